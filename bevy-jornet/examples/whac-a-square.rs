@@ -5,7 +5,7 @@ const TEXT: &str = "8ecae6";
 const SQUARE: &str = "219ebc";
 
 use bevy::{prelude::*, time::Stopwatch};
-use bevy_jornet::{JornetPlugin, Leaderboard};
+use bevy_jornet::JornetPlugin;
 
 fn main() {
     App::new()
@@ -17,10 +17,15 @@ fn main() {
         })
         .insert_resource(ClearColor(Color::hex(CLEAR).unwrap()))
         .add_plugins(DefaultPlugins)
-        .add_plugin(JornetPlugin::with_leaderboard(
-            option_env!("JORNET_LEADERBOARD_ID").unwrap_or("a920de64-3bdb-4f8e-87a8-e7bf20f00f81"),
-            option_env!("JORNET_LEADERBOARD_KEY").unwrap_or("a797039b-a91d-43e6-8e1c-94f9ca0aa1d6"),
-        ))
+        .add_plugin(
+            JornetPlugin::with_leaderboard(
+                option_env!("JORNET_LEADERBOARD_ID")
+                    .unwrap_or("a920de64-3bdb-4f8e-87a8-e7bf20f00f81"),
+                option_env!("JORNET_LEADERBOARD_KEY")
+                    .unwrap_or("a797039b-a91d-43e6-8e1c-94f9ca0aa1d6"),
+            )
+            .with_persistence("whac-a-square"),
+        )
         .add_startup_system(setup)
         .add_state(GameState::Menu)
         .add_plugin(menu::MenuPlugin)
@@ -36,9 +41,8 @@ enum GameState {
     Done,
 }
 
-fn setup(mut commands: Commands, mut leaderboard: ResMut<Leaderboard>) {
+fn setup(mut commands: Commands) {
     commands.spawn_bundle(Camera2dBundle::default());
-    leaderboard.create_player(None);
 }
 
 mod menu {
@@ -258,6 +262,7 @@ mod menu {
         }
     }
 
+    #[allow(clippy::type_complexity)]
     fn button_system(
         mut interaction_query: Query<
             (&Interaction, &mut UiColor),
@@ -0,0 +1,136 @@
+use std::sync::{Arc, RwLock};
+
+use jornet_client::{ClientError, Player};
+
+/// Outcome of an operation on the current player.
+///
+/// The [`Leaderboard`](crate::Leaderboard) resource is already updated when a success is
+/// received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerEvent {
+    /// The player was created by [`Leaderboard::create_player`](crate::Leaderboard::create_player).
+    Created(Player),
+    /// The player was recovered by
+    /// [`Leaderboard::recover_player`](crate::Leaderboard::recover_player).
+    Recovered(Player),
+    /// The player was renamed by
+    /// [`Leaderboard::rename_player`](crate::Leaderboard::rename_player).
+    Renamed(Player),
+    /// Another player was merged into this one by
+    /// [`Leaderboard::merge_player`](crate::Leaderboard::merge_player).
+    Merged(Player),
+    /// Creating the player failed.
+    CreationFailed(ClientError),
+    /// The recovery code was refused, or the server could not be reached.
+    RecoveryFailed(ClientError),
+    /// Renaming the player failed, for example because the name is not allowed.
+    RenameFailed(ClientError),
+    /// Merging the players failed.
+    MergeFailed(ClientError),
+}
+
+/// Outcome of sending a score with [`Leaderboard::send_score`](crate::Leaderboard::send_score)
+/// or [`Leaderboard::send_score_with_meta`](crate::Leaderboard::send_score_with_meta).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScoreEvent {
+    /// The score was saved by the server.
+    Submitted {
+        /// The score.
+        score: f32,
+        /// Metadata sent with the score.
+        meta: Option<String>,
+    },
+    /// The score could not be sent, for example because the network is down. It will be sent
+    /// again later.
+    Delayed {
+        /// The score.
+        score: f32,
+        /// Metadata sent with the score.
+        meta: Option<String>,
+        /// Why the score could not be sent.
+        error: ClientError,
+    },
+    /// The score was refused by the server, it won't be sent again.
+    Rejected {
+        /// The score.
+        score: f32,
+        /// Metadata sent with the score.
+        meta: Option<String>,
+        /// Why the score was refused, for example because the player is banned, the score is
+        /// out of bounds or it waited too long to be sent. Use [`ClientError::code`] to match
+        /// on it.
+        error: ClientError,
+    },
+}
+
+/// Outcome of getting data from the server.
+///
+/// The [`Leaderboard`](crate::Leaderboard) resource is already updated when a success is
+/// received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshEvent {
+    /// The clock was synchronized with the server by
+    /// [`Leaderboard::sync_clock`](crate::Leaderboard::sync_clock).
+    ClockSynced,
+    /// The leaderboard was refreshed by
+    /// [`Leaderboard::refresh_leaderboard`](crate::Leaderboard::refresh_leaderboard).
+    Leaderboard,
+    /// The ranking of the player was refreshed by
+    /// [`Leaderboard::refresh_player_ranking`](crate::Leaderboard::refresh_player_ranking).
+    PlayerRanking,
+    /// The profile of the player was refreshed by
+    /// [`Leaderboard::refresh_player_profile`](crate::Leaderboard::refresh_player_profile).
+    PlayerProfile,
+    /// Synchronizing the clock failed.
+    ClockSyncFailed(ClientError),
+    /// Refreshing the leaderboard failed.
+    LeaderboardFailed(ClientError),
+    /// Refreshing the ranking of the player failed, for example because they have no score yet.
+    PlayerRankingFailed(ClientError),
+    /// Refreshing the profile of the player failed.
+    PlayerProfileFailed(ClientError),
+}
+
+/// Event sent from a task, waiting to be sent to the game.
+#[derive(Debug, Clone)]
+pub(crate) enum PendingEvent {
+    Player(PlayerEvent),
+    Score(ScoreEvent),
+    Refresh(RefreshEvent),
+}
+
+impl From<PlayerEvent> for PendingEvent {
+    fn from(event: PlayerEvent) -> Self {
+        PendingEvent::Player(event)
+    }
+}
+
+impl From<ScoreEvent> for PendingEvent {
+    fn from(event: ScoreEvent) -> Self {
+        PendingEvent::Score(event)
+    }
+}
+
+impl From<RefreshEvent> for PendingEvent {
+    fn from(event: RefreshEvent) -> Self {
+        PendingEvent::Refresh(event)
+    }
+}
+
+/// Events sent from tasks, until they are sent to the game by
+/// [`done_refreshing_leaderboard`](crate::done_refreshing_leaderboard).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventQueue(Arc<RwLock<Vec<PendingEvent>>>);
+
+impl EventQueue {
+    pub(crate) fn send(&self, event: impl Into<PendingEvent>) {
+        self.0.write().unwrap().push(event.into());
+    }
+
+    pub(crate) fn drain(&self) -> Vec<PendingEvent> {
+        if self.0.try_read().map(|v| v.is_empty()).unwrap_or(true) {
+            return vec![];
+        }
+        self.0.write().unwrap().drain(..).collect()
+    }
+}
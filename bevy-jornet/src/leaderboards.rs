@@ -1,86 +1,292 @@
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::{SystemTime, UNIX_EPOCH};
-use std::{
-    cmp::Ordering,
-    sync::{Arc, RwLock},
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, RwLock,
 };
 
 use bevy::{
-    prelude::{warn, ResMut},
+    prelude::{warn, EventWriter, Res, ResMut},
     tasks::IoTaskPool,
+    time::Time,
+};
+use jornet_client::{
+    ClientError, JornetClient, Player, PlayerProfile, PlayerRanking, Score, ScoreHistoryQuery,
+    ScoreInput, ScoresQuery, TimeWindow,
 };
-use hmac::{Hmac, Mac};
-use serde::{Deserialize, Serialize};
-use sha2::Sha256;
 use uuid::Uuid;
 
-use crate::http;
+use crate::{
+    events::{EventQueue, PendingEvent},
+    outbox::{Outbox, PendingScore},
+    storage::Storage,
+    PlayerEvent, RefreshEvent, ScoreEvent,
+};
 
 /// Leaderboard resource, used to interact with Jornet leaderboard.
 pub struct Leaderboard {
     id: Uuid,
     key: Uuid,
     leaderboard: Vec<Score>,
-    updating: Arc<RwLock<Vec<Score>>>,
-    host: String,
+    updating: Arc<RwLock<Option<Vec<Score>>>>,
+    client: JornetClient,
+    clock_synced: Arc<AtomicBool>,
     new_player: Arc<RwLock<Option<Player>>>,
     player: Option<Player>,
+    new_recovery_code: Arc<RwLock<Option<String>>>,
+    recovery_code: Option<String>,
+    player_ranking: Option<PlayerRanking>,
+    updating_player_ranking: Arc<RwLock<Option<PlayerRanking>>>,
+    player_profile: Option<PlayerProfile>,
+    updating_player_profile: Arc<RwLock<Option<PlayerProfile>>>,
+    utc_offset: i32,
+    storage: Option<Storage>,
+    outbox: Outbox,
+    events: EventQueue,
 }
 
 impl Leaderboard {
-    pub(crate) fn with_leaderboard(id: Uuid, key: Uuid) -> Self {
+    pub(crate) fn with_leaderboard(id: Uuid, key: Uuid, client: JornetClient) -> Self {
         Self {
             id,
             key,
             leaderboard: Default::default(),
             updating: Default::default(),
-            host: "https://jornet.vleue.com".to_string(),
+            client,
+            clock_synced: Default::default(),
             new_player: Default::default(),
             player: Default::default(),
+            new_recovery_code: Default::default(),
+            recovery_code: Default::default(),
+            player_ranking: Default::default(),
+            updating_player_ranking: Default::default(),
+            player_profile: Default::default(),
+            updating_player_profile: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            utc_offset: 0,
+            #[cfg(target_arch = "wasm32")]
+            utc_offset: -(js_sys::Date::new_0().get_timezone_offset() as i32),
+            storage: None,
+            outbox: Default::default(),
+            events: Default::default(),
         }
     }
 
+    pub(crate) fn with_storage(mut self, storage: Storage) -> Self {
+        self.outbox = Outbox::with_storage(storage.clone());
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Connect as the saved player, or create one if none was saved yet.
+    pub(crate) fn load_or_create_player(&mut self) {
+        match self.storage.as_ref().and_then(Storage::load_player) {
+            Some(player) => self.as_player(player),
+            None => self.create_player(None),
+        }
+    }
+
+    fn set_player(&mut self, player: Player) {
+        if let Some(storage) = self.storage.as_ref() {
+            storage.save_player(&player);
+        }
+        self.player = Some(player);
+    }
+
+    /// Get the ID of the leaderboard.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Get the server time to correct the timestamp of scores if the local clock is off, as
+    /// scores too far from the server time are rejected.
+    ///
+    /// This is done automatically when the plugin starts, and again once a score is sent if the
+    /// server could not be reached then.
+    pub fn sync_clock(&self) {
+        let thread_pool = IoTaskPool::get();
+        let client = self.client.clone();
+        let clock_synced = self.clock_synced.clone();
+        let events = self.events.clone();
+
+        thread_pool
+            .spawn(async move {
+                match client.sync_clock().await {
+                    Ok(()) => {
+                        clock_synced.store(true, Ordering::Relaxed);
+                        events.send(RefreshEvent::ClockSynced);
+                    }
+                    Err(error) => {
+                        clock_synced.store(false, Ordering::Relaxed);
+                        warn!("error getting the server time: {}", error);
+                        events.send(RefreshEvent::ClockSyncFailed(error));
+                    }
+                }
+            })
+            .detach();
+    }
+
+    /// Set the offset from UTC, in minutes, of the timezone used to find the start of a day,
+    /// week or month for [`TimeWindow`]. Defaults to UTC on native, and to the browser timezone
+    /// in wasm.
+    pub fn set_utc_offset(&mut self, minutes: i32) {
+        self.utc_offset = minutes;
+    }
+
     /// Get the current player name.
     ///
     /// This can be used to get the random name generated if one was not specified when
     /// creating the player, or to save the `id`/`key` locally to be able to reconnect later
-    /// as the same player.
+    /// as the same player. The plugin can save it for you, see
+    /// [`JornetPlugin::with_persistence`](crate::JornetPlugin::with_persistence).
     pub fn get_player(&self) -> Option<&Player> {
         self.player.as_ref()
     }
 
+    /// Get the recovery code of the current player, if they were created or recovered during
+    /// this session.
+    ///
+    /// Show it to the player so they can get their player back on another device with
+    /// [`Self::recover_player`].
+    pub fn get_recovery_code(&self) -> Option<&str> {
+        self.recovery_code.as_deref()
+    }
+
     /// Create a player. If you don't specify a name, one will be genertaed randomly.
     ///
     /// Either this or [`Self::as_player`] must be called before sending a score.
     pub fn create_player(&mut self, name: Option<&str>) {
         let thread_pool = IoTaskPool::get();
-        let host = self.host.clone();
+        let client = self.client.clone();
 
-        let player = PlayerInput {
-            name: name.map(|n| n.to_string()),
-        };
+        let name = name.map(|n| n.to_string());
         let complete_player = self.new_player.clone();
+        let complete_recovery_code = self.new_recovery_code.clone();
+        let events = self.events.clone();
 
         thread_pool
             .spawn(async move {
-                if let Some(player) = http::post(&format!("{}/api/v1/players", host), player).await
-                {
-                    *complete_player.write().unwrap() = Some(player);
-                } else {
-                    warn!("error creating a player");
+                match client.create_player(name.as_deref()).await {
+                    Ok(new_player) => {
+                        *complete_recovery_code.write().unwrap() = Some(new_player.recovery_code);
+                        *complete_player.write().unwrap() = Some(new_player.player.clone());
+                        events.send(PlayerEvent::Created(new_player.player));
+                    }
+                    Err(error) => {
+                        warn!("error creating a player: {}", error);
+                        events.send(PlayerEvent::CreationFailed(error));
+                    }
+                }
+            })
+            .detach();
+    }
+
+    /// Get a player back from their recovery code, for example on a new device.
+    ///
+    /// This is done asynchronously, the player returned by [`Self::get_player`] is set once
+    /// the server accepted the code. The code can't be used again, the new one is available
+    /// with [`Self::get_recovery_code`].
+    pub fn recover_player(&mut self, recovery_code: &str) {
+        let thread_pool = IoTaskPool::get();
+        let client = self.client.clone();
+
+        let recovery_code = recovery_code.to_string();
+        let complete_player = self.new_player.clone();
+        let complete_recovery_code = self.new_recovery_code.clone();
+        let events = self.events.clone();
+
+        thread_pool
+            .spawn(async move {
+                match client.recover_player(&recovery_code).await {
+                    Ok(new_player) => {
+                        *complete_recovery_code.write().unwrap() = Some(new_player.recovery_code);
+                        *complete_player.write().unwrap() = Some(new_player.player.clone());
+                        events.send(PlayerEvent::Recovered(new_player.player));
+                    }
+                    Err(error) => {
+                        warn!("error recovering the player: {}", error);
+                        events.send(PlayerEvent::RecoveryFailed(error));
+                    }
+                }
+            })
+            .detach();
+    }
+
+    /// Merge `other` into the current player: the scores of `other` are moved to the current
+    /// player, and `other` is deleted. This can be used when a player played on several devices
+    /// before recovering their player.
+    ///
+    /// Returns `None` if no player has been set, see [`Self::create_player`] and
+    /// [`Self::as_player`].
+    pub fn merge_player(&self, other: Player) -> Option<()> {
+        let thread_pool = IoTaskPool::get();
+        let client = self.client.clone();
+
+        let player = self.player.clone()?;
+        let events = self.events.clone();
+
+        thread_pool
+            .spawn(async move {
+                match client.merge_players(&player, &other).await {
+                    Ok(player) => events.send(PlayerEvent::Merged(player)),
+                    Err(error) => {
+                        warn!("error merging the players: {}", error);
+                        events.send(PlayerEvent::MergeFailed(error));
+                    }
+                }
+            })
+            .detach();
+        Some(())
+    }
+
+    /// Change the name of the current player.
+    ///
+    /// This is done asynchronously, the player returned by [`Self::get_player`] is updated once
+    /// the server accepted the new name.
+    ///
+    /// Returns `None` if no player has been set, see [`Self::create_player`] and
+    /// [`Self::as_player`].
+    pub fn rename_player(&self, name: &str) -> Option<()> {
+        let thread_pool = IoTaskPool::get();
+        let client = self.client.clone();
+
+        let player = self.player.clone()?;
+        let name = name.to_string();
+        let complete_player = self.new_player.clone();
+        let events = self.events.clone();
+
+        thread_pool
+            .spawn(async move {
+                match client.rename_player(&player, &name).await {
+                    Ok(player) => {
+                        *complete_player.write().unwrap() = Some(player.clone());
+                        events.send(PlayerEvent::Renamed(player));
+                    }
+                    Err(error) => {
+                        warn!("error renaming the player: {}", error);
+                        events.send(PlayerEvent::RenameFailed(error));
+                    }
                 }
             })
             .detach();
+        Some(())
     }
 
     /// Connect as a returning player.
     ///
     /// Either this or [`Self::create_player`] must be called before sending a score.
     pub fn as_player(&mut self, player: Player) {
-        self.player = Some(player);
+        self.set_player(player);
+        self.recovery_code = None;
     }
 
     /// Send a score to the leaderboard.
+    ///
+    /// The score is signed right away and queued. If it can't be sent, for example because the
+    /// network is down, it's sent again later until the server saves or refuses it. With
+    /// [`JornetPlugin::with_persistence`](crate::JornetPlugin::with_persistence), scores not sent
+    /// yet are kept when the game is closed.
+    ///
+    /// Returns `None` if no player has been set, see [`Self::create_player`] and
+    /// [`Self::as_player`], or if the score is not a finite number.
     pub fn send_score(&self, score: f32) -> Option<()> {
         self.inner_send_score_with_meta(score, None)
     }
@@ -93,28 +299,70 @@ impl Leaderboard {
     }
 
     fn inner_send_score_with_meta(&self, score: f32, meta: Option<String>) -> Option<()> {
-        let thread_pool = IoTaskPool::get();
-        let leaderboard_id = self.id;
-        let host = self.host.clone();
-
-        if let Some(player) = self.player.as_ref() {
-            let score_to_send = ScoreInput::new(self.key, score, player, meta);
-            thread_pool
-                .spawn(async move {
-                    if http::post::<_, ()>(
-                        &format!("{}/api/v1/scores/{}", host, leaderboard_id),
-                        score_to_send,
-                    )
-                    .await
-                    .is_none()
-                    {
-                        warn!("error sending the score");
-                    }
-                })
-                .detach();
-            Some(())
-        } else {
-            None
+        let player = self.player.as_ref()?;
+        if !score.is_finite() {
+            warn!("score {} is not a finite number, it won't be sent", score);
+            return None;
+        }
+        self.outbox.push(
+            self.client.sign_score(self.key, player, score, meta),
+            player.clone(),
+        );
+        Some(())
+    }
+
+    fn handle_score_result(&self, sent: ScoreInput, result: Result<(), ClientError>, now: f64) {
+        if !matches!(result, Err(ClientError::Unreachable)) {
+            self.sync_clock_if_needed();
+        }
+        let error = match result {
+            Ok(()) => {
+                self.outbox.remove(&sent);
+                let ScoreInput { score, meta, .. } = sent;
+                self.events.send(ScoreEvent::Submitted { score, meta });
+                return;
+            }
+            Err(error) => error,
+        };
+        match error.code() {
+            // an earlier attempt was saved, but its response was lost
+            Some("duplicate_score") => {
+                self.outbox.remove(&sent);
+                let ScoreInput { score, meta, .. } = sent;
+                self.events.send(ScoreEvent::Submitted { score, meta });
+            }
+            // the server could not be reached or failed, it may work later
+            None | Some("internal_error") => {
+                warn!("error sending the score, it will be sent again: {}", error);
+                self.outbox.retry(&sent, now);
+                let ScoreInput { score, meta, .. } = sent;
+                self.events.send(ScoreEvent::Delayed { score, meta, error });
+            }
+            // the clock may have changed since it was synchronized
+            Some("timestamp_out_of_window") if self.outbox.retry_after_clock_sync(&sent, now) => {
+                warn!(
+                    "score refused for its timestamp, it will be sent again: {}",
+                    error
+                );
+                self.sync_clock();
+                let ScoreInput { score, meta, .. } = sent;
+                self.events.send(ScoreEvent::Delayed { score, meta, error });
+            }
+            _ => {
+                warn!("score refused by the server: {}", error);
+                self.outbox.remove(&sent);
+                let ScoreInput { score, meta, .. } = sent;
+                self.events
+                    .send(ScoreEvent::Rejected { score, meta, error });
+            }
+        }
+    }
+
+    /// Synchronize the clock if it was not done yet, for example because the game started
+    /// offline.
+    fn sync_clock_if_needed(&self) {
+        if !self.clock_synced.swap(true, Ordering::Relaxed) {
+            self.sync_clock();
         }
     }
 
@@ -124,28 +372,135 @@ impl Leaderboard {
     /// once the leaderboard data is available. You can then get those data with
     /// [`Self::get_leaderboard`].
     pub fn refresh_leaderboard(&self) {
+        self.refresh_leaderboard_in(TimeWindow::AllTime);
+    }
+
+    /// Refresh the leaderboard with only the scores submitted during a [`TimeWindow`], to
+    /// display a daily or weekly leaderboard.
+    ///
+    /// This is done asynchronously, the same way as [`Self::refresh_leaderboard`].
+    pub fn refresh_leaderboard_in(&self, window: TimeWindow) {
         let thread_pool = IoTaskPool::get();
         let leaderboard_id = self.id;
-        let host = self.host.clone();
+        let client = self.client.clone();
+        let query = ScoresQuery {
+            limit: Some(10),
+            window,
+            utc_offset: self.utc_offset,
+            player: self.player.clone(),
+        };
 
         let leaderboard_to_update = self.updating.clone();
+        let events = self.events.clone();
+
+        thread_pool
+            .spawn(async move {
+                match client.get_scores(leaderboard_id, &query).await {
+                    Ok(scores) => {
+                        *leaderboard_to_update.write().unwrap() = Some(scores);
+                        events.send(RefreshEvent::Leaderboard);
+                    }
+                    Err(error) => {
+                        warn!("error getting the leaderboard: {}", error);
+                        events.send(RefreshEvent::LeaderboardFailed(error));
+                    }
+                }
+            })
+            .detach();
+    }
+
+    /// Refresh the ranking of the current player, with `count` scores above and below their best
+    /// score.
+    ///
+    /// This is done asynchronously, the resource [`Leaderboard`] will be marked as changed
+    /// once the ranking is available. You can then get it with [`Self::get_player_ranking`].
+    ///
+    /// Returns `None` if no player has been set, see [`Self::create_player`] and
+    /// [`Self::as_player`].
+    pub fn refresh_player_ranking(&self, count: u32) -> Option<()> {
+        let thread_pool = IoTaskPool::get();
+        let leaderboard_id = self.id;
+        let client = self.client.clone();
+        let player = self.player.clone()?;
+
+        let ranking_to_update = self.updating_player_ranking.clone();
+        let events = self.events.clone();
 
         thread_pool
             .spawn(async move {
-                if let Some(scores) =
-                    http::get(&format!("{}/api/v1/scores/{}", host, leaderboard_id)).await
+                match client
+                    .get_player_ranking(leaderboard_id, &player, count)
+                    .await
                 {
-                    *leaderboard_to_update.write().unwrap() = scores;
-                } else {
-                    warn!("error getting the leaderboard");
+                    Ok(ranking) => {
+                        *ranking_to_update.write().unwrap() = Some(ranking);
+                        events.send(RefreshEvent::PlayerRanking);
+                    }
+                    Err(error) => {
+                        warn!("error getting the player ranking: {}", error);
+                        events.send(RefreshEvent::PlayerRankingFailed(error));
+                    }
+                }
+            })
+            .detach();
+        Some(())
+    }
+
+    /// Get the ranking of the current player. It must be refreshed first with
+    /// [`Self::refresh_player_ranking`].
+    ///
+    /// This can be used to show players outside of the top 10 where they are in the leaderboard.
+    pub fn get_player_ranking(&self) -> Option<&PlayerRanking> {
+        self.player_ranking.as_ref()
+    }
+
+    /// Refresh the profile of the current player, with their scores selected by `query`. Set
+    /// [`ScoreHistoryQuery::leaderboard`] to [`Self::id`] to only get the scores of this
+    /// leaderboard, and sort them by [`Best`](crate::ScoreHistoryOrder::Best) to show "your
+    /// best runs".
+    ///
+    /// This is done asynchronously, the resource [`Leaderboard`] will be marked as changed
+    /// once the profile is available. You can then get it with [`Self::get_player_profile`].
+    ///
+    /// Returns `None` if no player has been set, see [`Self::create_player`] and
+    /// [`Self::as_player`].
+    pub fn refresh_player_profile(&self, query: ScoreHistoryQuery) -> Option<()> {
+        let thread_pool = IoTaskPool::get();
+        let client = self.client.clone();
+        let player = self.player.clone()?;
+
+        let profile_to_update = self.updating_player_profile.clone();
+        let events = self.events.clone();
+
+        thread_pool
+            .spawn(async move {
+                match client.get_player_profile(&player, &query).await {
+                    Ok(profile) => {
+                        *profile_to_update.write().unwrap() = Some(profile);
+                        events.send(RefreshEvent::PlayerProfile);
+                    }
+                    Err(error) => {
+                        warn!("error getting the player profile: {}", error);
+                        events.send(RefreshEvent::PlayerProfileFailed(error));
+                    }
                 }
             })
             .detach();
+        Some(())
+    }
+
+    /// Get the profile of the current player. It must be refreshed first with
+    /// [`Self::refresh_player_profile`].
+    pub fn get_player_profile(&self) -> Option<&PlayerProfile> {
+        self.player_profile.as_ref()
     }
 
     /// Get the leaderboard data. It must be refreshed first with [`Self::refresh_leaderboard`],
     /// which will mark the [`Leaderboard`] resource as changed once the data has been refreshed.
     ///
+    /// Scores are sorted by the server following the sort order configured for the leaderboard,
+    /// best score first, and limited to the top 10.
+    ///
     /// Example system:
     ///
     /// ```rust
@@ -167,26 +522,55 @@ impl Leaderboard {
     }
 }
 
-/// System to handle refreshing the [`Leaderboard`] resource when new data is available.
+/// System sending the scores waiting in the queue of the [`Leaderboard`] resource, and
+/// handling the responses of the server.
+/// It is automatically added by the [`JornetPlugin`](crate::JornetPlugin) in stage
+/// [`CoreStage::Update`](bevy::prelude::CoreStage).
+pub fn send_queued_scores(leaderboard: Res<Leaderboard>, time: Res<Time>) {
+    let now = time.seconds_since_startup();
+    for (sent, result) in leaderboard.outbox.take_results() {
+        leaderboard.handle_score_result(sent, result, now);
+    }
+
+    let thread_pool = IoTaskPool::get();
+    for PendingScore { score, player } in leaderboard.outbox.due(now) {
+        let leaderboard_id = leaderboard.id;
+        let client = leaderboard.client.clone();
+        let outbox = leaderboard.outbox.clone();
+        thread_pool
+            .spawn(async move {
+                let result = client
+                    .submit_delayed_score(leaderboard_id, &player, &score)
+                    .await;
+                outbox.report(score, result);
+            })
+            .detach();
+    }
+}
+
+/// System to handle refreshing the [`Leaderboard`] resource when new data is available, and
+/// sending the [`PlayerEvent`], [`ScoreEvent`] and [`RefreshEvent`] events.
 /// It is automatically added by the [`JornetPlugin`](crate::JornetPlugin) in stage
 /// [`CoreStage::Update`](bevy::prelude::CoreStage).
-pub fn done_refreshing_leaderboard(mut leaderboard: ResMut<Leaderboard>) {
-    if !leaderboard
+pub fn done_refreshing_leaderboard(
+    mut leaderboard: ResMut<Leaderboard>,
+    mut player_events: EventWriter<PlayerEvent>,
+    mut score_events: EventWriter<ScoreEvent>,
+    mut refresh_events: EventWriter<RefreshEvent>,
+) {
+    // taken before the data so that the data of an event is always there when it's sent
+    let events = leaderboard.events.drain();
+
+    if leaderboard
         .updating
         .try_read()
-        .map(|v| v.is_empty())
-        .unwrap_or(true)
+        .map(|v| v.is_some())
+        .unwrap_or(false)
     {
-        let mut updated = leaderboard
-            .updating
-            .write()
-            .unwrap()
-            .drain(..)
-            .collect::<Vec<_>>();
-        updated
-            .sort_unstable_by(|s1, s2| s2.score.partial_cmp(&s1.score).unwrap_or(Ordering::Equal));
-        updated.truncate(10);
-        leaderboard.leaderboard = updated;
+        let updated = leaderboard.updating.write().unwrap().take();
+        if let Some(updated) = updated {
+            leaderboard.leaderboard = updated;
+        }
     }
     if leaderboard
         .new_player
@@ -195,70 +579,38 @@ pub fn done_refreshing_leaderboard(mut leaderboard: ResMut<Leaderboard>) {
         .unwrap_or(false)
     {
         let new_player = leaderboard.new_player.write().unwrap().take();
-        leaderboard.player = new_player;
-    }
-}
-
-/// A score from a leaderboard
-#[derive(Deserialize, Debug, Clone)]
-pub struct Score {
-    /// The score.
-    pub score: f32,
-    /// The player name.
-    pub player: String,
-    /// Optional metadata.
-    pub meta: Option<String>,
-    /// Timestamp of the score.
-    pub timestamp: String,
-}
-
-#[derive(Serialize)]
-struct ScoreInput {
-    pub score: f32,
-    pub player: Uuid,
-    pub meta: Option<String>,
-    pub timestamp: u64,
-    pub k: String,
-}
-
-impl ScoreInput {
-    fn new(leaderboard_key: Uuid, score: f32, player: &Player, meta: Option<String>) -> Self {
-        #[cfg(not(target_arch = "wasm32"))]
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs();
-        #[cfg(target_arch = "wasm32")]
-        let timestamp = (js_sys::Date::now() / 1000.0) as u64;
-
-        let mut mac = Hmac::<Sha256>::new_from_slice(player.key.as_bytes()).unwrap();
-        mac.update(&timestamp.to_le_bytes());
-        mac.update(leaderboard_key.as_bytes());
-        mac.update(player.id.as_bytes());
-        mac.update(&score.to_le_bytes());
-        if let Some(meta) = meta.as_ref() {
-            mac.update(meta.as_bytes());
+        if let Some(player) = new_player {
+            leaderboard.set_player(player);
         }
-
-        let hmac = hex::encode(&mac.finalize().into_bytes()[..]);
-        Self {
-            score,
-            player: player.id,
-            meta,
-            timestamp,
-            k: hmac,
+        let new_recovery_code = leaderboard.new_recovery_code.write().unwrap().take();
+        if new_recovery_code.is_some() {
+            leaderboard.recovery_code = new_recovery_code;
         }
     }
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Player {
-    pub id: Uuid,
-    pub key: Uuid,
-    pub name: String,
-}
+    if leaderboard
+        .updating_player_ranking
+        .try_read()
+        .map(|v| v.is_some())
+        .unwrap_or(false)
+    {
+        let player_ranking = leaderboard.updating_player_ranking.write().unwrap().take();
+        leaderboard.player_ranking = player_ranking;
+    }
+    if leaderboard
+        .updating_player_profile
+        .try_read()
+        .map(|v| v.is_some())
+        .unwrap_or(false)
+    {
+        let player_profile = leaderboard.updating_player_profile.write().unwrap().take();
+        leaderboard.player_profile = player_profile;
+    }
 
-#[derive(Serialize, Debug, Clone)]
-struct PlayerInput {
-    pub name: Option<String>,
+    for event in events {
+        match event {
+            PendingEvent::Player(event) => player_events.send(event),
+            PendingEvent::Score(event) => score_events.send(event),
+            PendingEvent::Refresh(event) => refresh_events.send(event),
+        }
+    }
 }
@@ -4,21 +4,39 @@
 //!
 //! Bevy Plugin to integrate with [Jornet](https://jornet.vleue.com)
 //! - save high scores
+//! - recover players on another device
 //! - get a leaderboard
 
-use bevy::prelude::{App, Plugin};
+use std::time::Duration;
+
+use bevy::prelude::{App, Plugin, Res, ResMut};
+use jornet_client::{HttpOptions, JornetClient, DEFAULT_HOST};
 pub use leaderboards::Leaderboard;
+use storage::Storage;
 use uuid::Uuid;
 
-mod http;
+mod events;
 mod leaderboards;
+mod outbox;
+mod storage;
+
+pub use events::{PlayerEvent, RefreshEvent, ScoreEvent};
+pub use jornet_client::{
+    ClientError, Player, PlayerProfile, PlayerRanking, PlayerScore, Score, ScoreHistoryOrder,
+    ScoreHistoryQuery, TimeWindow,
+};
+pub use leaderboards::{done_refreshing_leaderboard, send_queued_scores};
 
-pub use leaderboards::{done_refreshing_leaderboard, Score};
+/// Environment variable overriding the URL of the server, see [`JornetPlugin::with_host`].
+pub const HOST_ENV_VAR: &str = "JORNET_HOST";
 
 /// Bevy Plugin handling communications with the Jornet server.
 pub struct JornetPlugin {
     leaderboard: Uuid,
     key: Uuid,
+    persistence: Option<String>,
+    host: Option<String>,
+    options: HttpOptions,
 }
 
 impl JornetPlugin {
@@ -32,14 +50,88 @@ impl JornetPlugin {
         Self {
             leaderboard: Uuid::parse_str(id).expect("invalid leaderboard ID"),
             key: Uuid::parse_str(key).expect("invalid leaderboard key"),
+            persistence: None,
+            host: None,
+            options: Default::default(),
         }
     }
+
+    /// Use the server at `host`, for example `http://localhost:8080` for a self-hosted server.
+    /// Defaults to <https://jornet.vleue.com>.
+    ///
+    /// The [`HOST_ENV_VAR`] environment variable takes precedence, so that the server can be
+    /// changed without changing the game. On native it's read when the game starts, then when
+    /// the game is built. In wasm, it's only read when the game is built.
+    pub fn with_host(mut self, host: &str) -> Self {
+        self.host = Some(host.to_string());
+        self
+    }
+
+    /// Abort requests that take longer than `timeout`. There is no timeout by default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Send `user_agent` with requests. It's only used on native, browsers send their own.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.options.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    fn host(&self) -> String {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(host) = std::env::var(HOST_ENV_VAR)
+            .ok()
+            .filter(|host| !host.is_empty())
+        {
+            return host;
+        }
+        option_env!("JORNET_HOST")
+            .filter(|host| !host.is_empty())
+            .or(self.host.as_deref())
+            .unwrap_or(DEFAULT_HOST)
+            .to_string()
+    }
+
+    /// Save the player and the scores not sent yet between sessions, so that the same player is
+    /// used each time the game is started.
+    ///
+    /// On startup, the saved player is [used](Leaderboard::as_player), or a player with a random
+    /// name is [created](Leaderboard::create_player) if none was saved yet. Every change of the
+    /// current player is saved. Scores that could not be sent before the game was closed are
+    /// sent again.
+    ///
+    /// On native, they are saved in files in a `game` directory in the config directory of the
+    /// platform. In wasm, they are saved in `localStorage`.
+    pub fn with_persistence(mut self, game: &str) -> Self {
+        self.persistence = Some(game.to_string());
+        self
+    }
 }
 
 impl Plugin for JornetPlugin {
     fn build(&self, app: &mut App) {
-        let leaderboard = Leaderboard::with_leaderboard(self.leaderboard, self.key);
+        let client = JornetClient::with_options(&self.host(), &self.options);
+        let mut leaderboard = Leaderboard::with_leaderboard(self.leaderboard, self.key, client);
+        if let Some(game) = self.persistence.as_deref() {
+            leaderboard = leaderboard.with_storage(Storage::new(game));
+            app.add_startup_system(load_or_create_player);
+        }
         app.insert_resource(leaderboard)
+            .add_event::<PlayerEvent>()
+            .add_event::<ScoreEvent>()
+            .add_event::<RefreshEvent>()
+            .add_startup_system(sync_clock)
+            .add_system(send_queued_scores)
             .add_system(done_refreshing_leaderboard);
     }
 }
+
+fn sync_clock(leaderboard: Res<Leaderboard>) {
+    leaderboard.sync_clock();
+}
+
+fn load_or_create_player(mut leaderboard: ResMut<Leaderboard>) {
+    leaderboard.load_or_create_player();
+}
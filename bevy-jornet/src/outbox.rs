@@ -0,0 +1,257 @@
+use std::sync::{Arc, Mutex};
+
+use jornet_client::{ClientError, Player, ScoreInput};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+/// Delay before the first retry of a score, in seconds. It doubles after each failure.
+const INITIAL_RETRY_DELAY: f64 = 1.0;
+/// Maximum delay between two attempts to send a score, in seconds.
+const MAX_RETRY_DELAY: f64 = 300.0;
+
+/// A score that was sent, with the response of the server.
+type ScoreResult = (ScoreInput, Result<(), ClientError>);
+
+/// Scores waiting to be saved by the server. They are retried with an exponential backoff
+/// until the server saves or refuses them, and saved with the [`Storage`] if there is one so
+/// they are not lost when the game is closed.
+///
+/// It's cheap to clone, clones share the same scores.
+#[derive(Clone, Default)]
+pub(crate) struct Outbox {
+    scores: Arc<Mutex<Vec<QueuedScore>>>,
+    results: Arc<Mutex<Vec<ScoreResult>>>,
+    storage: Option<Storage>,
+}
+
+/// A score waiting to be sent, with the player who signed it. The player signs the time it is
+/// sent at, so that a score that waited doesn't need to be signed again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PendingScore {
+    pub(crate) score: ScoreInput,
+    pub(crate) player: Player,
+}
+
+struct QueuedScore {
+    pending: PendingScore,
+    attempts: u32,
+    /// Time of the next attempt, in seconds since startup.
+    next_attempt: f64,
+    sending: bool,
+    /// The score was already sent again after synchronizing the clock.
+    resent_after_clock_sync: bool,
+}
+
+impl QueuedScore {
+    fn new(pending: PendingScore) -> Self {
+        Self {
+            pending,
+            attempts: 0,
+            next_attempt: 0.0,
+            sending: false,
+            resent_after_clock_sync: false,
+        }
+    }
+}
+
+impl Outbox {
+    /// Get the scores saved during a previous session, and save the scores from now on.
+    pub(crate) fn with_storage(storage: Storage) -> Self {
+        let scores = storage
+            .load_outbox()
+            .into_iter()
+            .map(QueuedScore::new)
+            .collect();
+        Self {
+            scores: Arc::new(Mutex::new(scores)),
+            results: Default::default(),
+            storage: Some(storage),
+        }
+    }
+
+    /// Queue a score signed by `player` to be sent as soon as possible.
+    pub(crate) fn push(&self, score: ScoreInput, player: Player) {
+        let mut scores = self.scores.lock().unwrap();
+        scores.push(QueuedScore::new(PendingScore { score, player }));
+        self.save(&scores);
+    }
+
+    /// Get the scores that should be sent at `now`. They won't be returned again until
+    /// [`Self::retry`] is called for them.
+    pub(crate) fn due(&self, now: f64) -> Vec<PendingScore> {
+        let mut scores = self.scores.lock().unwrap();
+        scores
+            .iter_mut()
+            .filter(|queued| !queued.sending && queued.next_attempt <= now)
+            .map(|queued| {
+                queued.sending = true;
+                queued.pending.clone()
+            })
+            .collect()
+    }
+
+    /// Keep the response of the server to a score, to be handled on the main thread.
+    pub(crate) fn report(&self, score: ScoreInput, result: Result<(), ClientError>) {
+        self.results.lock().unwrap().push((score, result));
+    }
+
+    /// Get the responses received since the last call.
+    pub(crate) fn take_results(&self) -> Vec<ScoreResult> {
+        std::mem::take(&mut *self.results.lock().unwrap())
+    }
+
+    /// Remove a score that the server saved or refused.
+    pub(crate) fn remove(&self, score: &ScoreInput) {
+        let mut scores = self.scores.lock().unwrap();
+        scores.retain(|queued| queued.pending.score.k != score.k);
+        self.save(&scores);
+    }
+
+    /// Send a score again later, after a delay growing with the number of attempts.
+    pub(crate) fn retry(&self, score: &ScoreInput, now: f64) {
+        let mut scores = self.scores.lock().unwrap();
+        if let Some(queued) = scores
+            .iter_mut()
+            .find(|queued| queued.pending.score.k == score.k)
+        {
+            let delay = INITIAL_RETRY_DELAY * 2.0_f64.powi(queued.attempts.min(16) as i32);
+            queued.attempts += 1;
+            queued.next_attempt = now + delay.min(MAX_RETRY_DELAY);
+            queued.sending = false;
+        }
+        self.save(&scores);
+    }
+
+    /// Send again a score refused because of its timestamp, once the clock is synchronized.
+    /// Returns `false` if it was already sent again for that reason, as the clock can't help it.
+    pub(crate) fn retry_after_clock_sync(&self, score: &ScoreInput, now: f64) -> bool {
+        {
+            let mut scores = self.scores.lock().unwrap();
+            match scores
+                .iter_mut()
+                .find(|queued| queued.pending.score.k == score.k)
+            {
+                Some(queued) if !queued.resent_after_clock_sync => {
+                    queued.resent_after_clock_sync = true;
+                }
+                _ => return false,
+            }
+        }
+        self.retry(score, now);
+        true
+    }
+
+    fn save(&self, scores: &[QueuedScore]) {
+        if let Some(storage) = self.storage.as_ref() {
+            storage.save_outbox(
+                &scores
+                    .iter()
+                    .map(|queued| queued.pending.clone())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn score(score: f32) -> (ScoreInput, Player) {
+        let player = Player {
+            id: Uuid::new_v4(),
+            name: "player".to_string(),
+            key: Uuid::new_v4(),
+        };
+        (
+            ScoreInput::new_at(score, player.clone(), None, Uuid::new_v4(), 1_660_000_000),
+            player,
+        )
+    }
+
+    fn due_scores(outbox: &Outbox, now: f64) -> Vec<f32> {
+        outbox
+            .due(now)
+            .into_iter()
+            .map(|pending| pending.score.score)
+            .collect()
+    }
+
+    #[test]
+    fn due_scores_are_sent_once() {
+        let outbox = Outbox::default();
+        let (first, player) = score(1.0);
+        outbox.push(first, player.clone());
+        let (second, _) = score(2.0);
+        outbox.push(second, player.clone());
+
+        let due = outbox.due(0.0);
+        assert_eq!(
+            due.iter().map(|p| p.score.score).collect::<Vec<_>>(),
+            [1.0, 2.0]
+        );
+        assert!(due.iter().all(|pending| pending.player == player));
+        // they are being sent
+        assert!(due_scores(&outbox, 10.0).is_empty());
+    }
+
+    #[test]
+    fn retry_after_a_growing_delay() {
+        let outbox = Outbox::default();
+        let (sent, player) = score(1.0);
+        outbox.push(sent.clone(), player);
+        outbox.due(0.0);
+
+        outbox.retry(&sent, 0.0);
+        assert!(due_scores(&outbox, 0.5).is_empty());
+        assert_eq!(due_scores(&outbox, INITIAL_RETRY_DELAY), [1.0]);
+
+        outbox.retry(&sent, 10.0);
+        assert!(due_scores(&outbox, 10.0 + INITIAL_RETRY_DELAY).is_empty());
+        assert_eq!(due_scores(&outbox, 10.0 + 2.0 * INITIAL_RETRY_DELAY), [1.0]);
+    }
+
+    #[test]
+    fn retry_delay_is_capped() {
+        let outbox = Outbox::default();
+        let (sent, player) = score(1.0);
+        outbox.push(sent.clone(), player);
+        for _ in 0..40 {
+            outbox.due(f64::MAX);
+            outbox.retry(&sent, 0.0);
+        }
+        assert!(due_scores(&outbox, MAX_RETRY_DELAY - 1.0).is_empty());
+        assert_eq!(due_scores(&outbox, MAX_RETRY_DELAY), [1.0]);
+    }
+
+    #[test]
+    fn retry_after_clock_sync_only_once() {
+        let outbox = Outbox::default();
+        let (sent, player) = score(1.0);
+        outbox.push(sent.clone(), player);
+        outbox.due(0.0);
+
+        assert!(outbox.retry_after_clock_sync(&sent, 0.0));
+        assert_eq!(due_scores(&outbox, INITIAL_RETRY_DELAY), [1.0]);
+        assert!(!outbox.retry_after_clock_sync(&sent, 10.0));
+    }
+
+    #[test]
+    fn removed_scores_are_not_sent() {
+        let outbox = Outbox::default();
+        let (first, player) = score(1.0);
+        outbox.push(first.clone(), player.clone());
+        let (second, _) = score(2.0);
+        outbox.push(second.clone(), player);
+
+        outbox.remove(&first);
+        assert_eq!(due_scores(&outbox, 0.0), [2.0]);
+        // a score being sent can be removed once the server saved it
+        outbox.remove(&second);
+        outbox.retry(&second, 0.0);
+        assert!(due_scores(&outbox, f64::MAX).is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    fs,
+    io::ErrorKind,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::warn;
+use jornet_client::Player;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::outbox::PendingScore;
+
+/// Where data is saved between sessions: files in the config directory of the platform on
+/// native, and `localStorage` in wasm.
+#[derive(Debug, Clone)]
+pub(crate) struct Storage {
+    game: String,
+}
+
+impl Storage {
+    pub(crate) fn new(game: &str) -> Self {
+        Self {
+            game: game.to_string(),
+        }
+    }
+
+    /// Get the saved player, if there is one.
+    pub(crate) fn load_player(&self) -> Option<Player> {
+        self.load("player")
+    }
+
+    /// Save the player, replacing the one already saved.
+    pub(crate) fn save_player(&self, player: &Player) {
+        self.save("player", player);
+    }
+
+    /// Get the scores that were not sent yet.
+    pub(crate) fn load_outbox(&self) -> Vec<PendingScore> {
+        self.load("outbox").unwrap_or_default()
+    }
+
+    /// Save the scores that were not sent yet, replacing the ones already saved.
+    pub(crate) fn save_outbox(&self, scores: &[PendingScore]) {
+        self.save("outbox", &scores);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn path(&self, name: &str) -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join(&self.game)
+                .join(format!("jornet_{}.json", name)),
+        )
+    }
+
+    /// Read what was saved under `name`. If it can't be read, it's set aside so that saving
+    /// something new doesn't lose it, as it may be a player that could be recovered by hand.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let path = self.path(name)?;
+        let error = match fs::read_to_string(&path) {
+            Ok(saved) => match serde_json::from_str(&saved) {
+                Ok(value) => return Some(value),
+                Err(error) => error.to_string(),
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => return None,
+            Err(error) => error.to_string(),
+        };
+        let backup = path.with_extension(format!("json.{}.bak", now()));
+        warn!(
+            "error loading the {} from {}, moving it to {}: {}",
+            name,
+            path.display(),
+            backup.display(),
+            error
+        );
+        if let Err(error) = fs::rename(&path, &backup) {
+            warn!("error moving {}: {}", path.display(), error);
+        }
+        None
+    }
+
+    /// Read what was saved under `name`. If it can't be read, it's set aside so that saving
+    /// something new doesn't lose it, as it may be a player that could be recovered by hand.
+    #[cfg(target_arch = "wasm32")]
+    fn load<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let storage = local_storage()?;
+        let key = self.key(name);
+        let saved = storage.get_item(&key).ok()??;
+        match serde_json::from_str(&saved) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                let backup = format!("{}_{}_bak", key, js_sys::Date::now() as u64 / 1000);
+                warn!(
+                    "error loading the {} from localStorage, moving it to {}: {}",
+                    name, backup, error
+                );
+                if storage.set_item(&backup, &saved).is_ok() {
+                    let _ = storage.remove_item(&key);
+                }
+                None
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save<T: Serialize>(&self, name: &str, value: &T) {
+        let path = match self.path(name) {
+            Some(path) => path,
+            None => {
+                warn!("no config directory to save the {}", name);
+                return;
+            }
+        };
+        // serializing a player or scores - can't fail
+        let saved = serde_json::to_string(value).unwrap();
+        let written = path
+            .parent()
+            .map(fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| fs::write(&path, saved));
+        if let Err(error) = written {
+            warn!("error saving the {} to {}: {}", name, path.display(), error);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn save<T: Serialize>(&self, name: &str, value: &T) {
+        // serializing a player or scores - can't fail
+        let saved = serde_json::to_string(value).unwrap();
+        if local_storage()
+            .and_then(|storage| storage.set_item(&self.key(name), &saved).ok())
+            .is_none()
+        {
+            warn!("error saving the {} to localStorage", name);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn key(&self, name: &str) -> String {
+        format!("jornet_{}_{}", name, self.game)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
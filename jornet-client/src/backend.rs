@@ -0,0 +1,217 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_futures::JsFuture;
+#[cfg(target_arch = "wasm32")]
+use web_sys::{AbortController, Headers, Request, RequestInit, Response};
+
+use crate::ClientError;
+
+/// Future returned by an [`HttpBackend`]. It must be `Send`, except in wasm where requests
+/// stay on the main thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+/// Future returned by an [`HttpBackend`]. It must be `Send`, except in wasm where requests
+/// stay on the main thread.
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// HTTP method of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+    /// `PUT`
+    Put,
+}
+
+/// Request sent to the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// HTTP method.
+    pub method: Method,
+    /// Full URL of the request.
+    pub url: String,
+    /// JSON body of the request. It must be sent with the `application/json` content type.
+    pub body: Option<String>,
+}
+
+/// Response received from the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Body of the response.
+    pub body: String,
+}
+
+/// Options of the default backends.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpOptions {
+    /// Maximum duration of a request. There is no timeout if not set.
+    pub timeout: Option<Duration>,
+    /// User agent sent with requests. It's only used on native, browsers send their own.
+    pub user_agent: Option<String>,
+}
+
+/// Transport used by [`JornetClient`](crate::JornetClient) to send requests.
+///
+/// A backend only sends requests and returns the responses as is, whatever their status. It
+/// should return [`ClientError::Unreachable`] if the server could not be reached.
+pub trait HttpBackend: Send + Sync {
+    /// Send a request.
+    fn request(&self, request: HttpRequest) -> BoxFuture<Result<HttpResponse, ClientError>>;
+}
+
+/// Backend using [`ureq`](https://docs.rs/ureq).
+///
+/// Requests are blocking, so the futures should be run on a thread that can block.
+#[cfg(all(feature = "ureq", not(target_arch = "wasm32")))]
+#[derive(Clone)]
+pub struct UreqBackend {
+    agent: ureq::Agent,
+}
+
+#[cfg(all(feature = "ureq", not(target_arch = "wasm32")))]
+impl UreqBackend {
+    /// Use a configured [`ureq::Agent`].
+    pub fn with_agent(agent: ureq::Agent) -> Self {
+        Self { agent }
+    }
+
+    /// Use an agent configured with [`HttpOptions`].
+    pub fn with_options(options: &HttpOptions) -> Self {
+        let mut agent = ureq::AgentBuilder::new();
+        if let Some(timeout) = options.timeout {
+            agent = agent.timeout(timeout);
+        }
+        if let Some(user_agent) = options.user_agent.as_deref() {
+            agent = agent.user_agent(user_agent);
+        }
+        Self::with_agent(agent.build())
+    }
+}
+
+#[cfg(all(feature = "ureq", not(target_arch = "wasm32")))]
+impl Default for UreqBackend {
+    fn default() -> Self {
+        Self::with_agent(ureq::Agent::new())
+    }
+}
+
+#[cfg(all(feature = "ureq", not(target_arch = "wasm32")))]
+impl HttpBackend for UreqBackend {
+    fn request(&self, request: HttpRequest) -> BoxFuture<Result<HttpResponse, ClientError>> {
+        let agent = self.agent.clone();
+        Box::pin(async move {
+            let response = match (request.method, request.body) {
+                (Method::Get, _) => agent.get(&request.url).call(),
+                (Method::Post, Some(body)) => agent
+                    .post(&request.url)
+                    .set("Content-Type", "application/json")
+                    .send_string(&body),
+                (Method::Post, None) => agent.post(&request.url).call(),
+                (Method::Put, Some(body)) => agent
+                    .put(&request.url)
+                    .set("Content-Type", "application/json")
+                    .send_string(&body),
+                (Method::Put, None) => agent.put(&request.url).call(),
+            };
+            let response = match response {
+                Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+                Err(ureq::Error::Transport(_)) => return Err(ClientError::Unreachable),
+            };
+            let status = response.status();
+            let body = response
+                .into_string()
+                .map_err(|_| ClientError::InvalidResponse)?;
+            Ok(HttpResponse { status, body })
+        })
+    }
+}
+
+/// Backend using `fetch` from the browser.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Default)]
+pub struct FetchBackend {
+    timeout: Option<Duration>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl FetchBackend {
+    /// Use the timeout from [`HttpOptions`]. Requests that take longer are aborted.
+    pub fn with_options(options: &HttpOptions) -> Self {
+        Self {
+            timeout: options.timeout,
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl HttpBackend for FetchBackend {
+    fn request(&self, request: HttpRequest) -> BoxFuture<Result<HttpResponse, ClientError>> {
+        let timeout = self.timeout;
+        Box::pin(async move {
+            let mut opts = RequestInit::new();
+            match request.method {
+                Method::Get => opts.method("GET"),
+                Method::Post => opts.method("POST"),
+                Method::Put => opts.method("PUT"),
+            };
+            if let Some(body) = request.body {
+                // building headers - can't fail
+                let headers = Headers::new().unwrap();
+                headers.set("Content-Type", "application/json").unwrap();
+                opts.body(Some(&JsValue::from_str(&body))).headers(&headers);
+            }
+
+            // getting the window - can't fail
+            let window = web_sys::window().unwrap();
+            // the request is aborted once the timeout expires
+            let abort = match (timeout, AbortController::new()) {
+                (Some(timeout), Ok(controller)) => {
+                    opts.signal(Some(&controller.signal()));
+                    let on_timeout = Closure::once(move || controller.abort());
+                    window
+                        .set_timeout_with_callback_and_timeout_and_arguments_0(
+                            on_timeout.as_ref().unchecked_ref(),
+                            timeout.as_millis().min(i32::MAX as u128) as i32,
+                        )
+                        .ok()
+                        .map(|handle| (handle, on_timeout))
+                }
+                _ => None,
+            };
+
+            // the timeout covers reading the body too, it's only cleared once it's read
+            let response = async {
+                let request = Request::new_with_str_and_init(&request.url, &opts)
+                    .map_err(|_| ClientError::Unreachable)?;
+                // can fail on network error or timeout
+                let resp_value = JsFuture::from(window.fetch_with_request(&request))
+                    .await
+                    .map_err(|_| ClientError::Unreachable)?;
+                // converting the JsValue to the correct type - can't fail
+                let resp: Response = resp_value.dyn_into().unwrap();
+                // reading the body can also fail on network error or timeout
+                let body = JsFuture::from(resp.text().map_err(|_| ClientError::InvalidResponse)?)
+                    .await
+                    .map_err(|_| ClientError::Unreachable)?;
+                Ok((resp, body))
+            }
+            .await;
+            if let Some((handle, _on_timeout)) = abort {
+                window.clear_timeout_with_handle(handle);
+            }
+            let (resp, body) = response?;
+            Ok(HttpResponse {
+                status: resp.status(),
+                body: body.as_string().unwrap_or_default(),
+            })
+        })
+    }
+}
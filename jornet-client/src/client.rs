@@ -0,0 +1,435 @@
+use std::sync::{
+    atomic::{AtomicI64, Ordering},
+    Arc,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jornet_protocol::{
+    ErrorBody, NewPlayer, Player, PlayerAuth, PlayerInput, PlayerMerge, PlayerProfile,
+    PlayerRanking, PlayerUpdate, RecoveryInput, Score, ScoreHistoryOrder, ScoreInput, ServerTime,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use uuid::Uuid;
+
+use crate::{ClientError, HttpBackend, HttpRequest, Method};
+
+/// Host of the public Jornet server.
+pub const DEFAULT_HOST: &str = "https://jornet.vleue.com";
+
+/// Client to a Jornet server.
+///
+/// It's cheap to clone, clones share the same backend and clock correction.
+#[derive(Clone)]
+pub struct JornetClient {
+    host: String,
+    backend: Arc<dyn HttpBackend>,
+    clock_offset: Arc<AtomicI64>,
+}
+
+impl JornetClient {
+    /// Create a client to the server at `host`, using `ureq` on native and `fetch` in wasm.
+    #[cfg(any(feature = "ureq", target_arch = "wasm32"))]
+    pub fn new(host: &str) -> Self {
+        Self::with_options(host, &crate::HttpOptions::default())
+    }
+
+    /// Create a client to the server at `host`, using `ureq` on native and `fetch` in wasm
+    /// configured with `options`.
+    #[cfg(all(feature = "ureq", not(target_arch = "wasm32")))]
+    pub fn with_options(host: &str, options: &crate::HttpOptions) -> Self {
+        Self::with_backend(host, crate::UreqBackend::with_options(options))
+    }
+
+    /// Create a client to the server at `host`, using `ureq` on native and `fetch` in wasm
+    /// configured with `options`.
+    #[cfg(target_arch = "wasm32")]
+    pub fn with_options(host: &str, options: &crate::HttpOptions) -> Self {
+        Self::with_backend(host, crate::FetchBackend::with_options(options))
+    }
+
+    /// Create a client to the server at `host`, sending requests with `backend`.
+    pub fn with_backend(host: &str, backend: impl HttpBackend + 'static) -> Self {
+        Self {
+            host: host.trim_end_matches('/').to_string(),
+            backend: Arc::new(backend),
+            clock_offset: Default::default(),
+        }
+    }
+
+    /// Host of the server.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<T, ClientError> {
+        let response = self
+            .backend
+            .request(HttpRequest {
+                method,
+                url: format!("{}/{}", self.host, path),
+                body,
+            })
+            .await?;
+        if (200..300).contains(&response.status) {
+            serde_json::from_str(&response.body).map_err(|_| ClientError::InvalidResponse)
+        } else {
+            Err(serde_json::from_str::<ErrorBody>(&response.body)
+                .map(ClientError::Api)
+                .unwrap_or(ClientError::InvalidResponse))
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ClientError> {
+        self.send(Method::Get, path, None).await
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, ClientError> {
+        // serializing the body - can't fail
+        let body = serde_json::to_string(body).unwrap();
+        self.send(Method::Post, path, Some(body)).await
+    }
+
+    async fn put<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, ClientError> {
+        // serializing the body - can't fail
+        let body = serde_json::to_string(body).unwrap();
+        self.send(Method::Put, path, Some(body)).await
+    }
+
+    /// Create a player. If you don't specify a name, one will be generated randomly.
+    ///
+    /// Save the returned [`Player`] to be able to send scores as the same player later, and show
+    /// the recovery code to the player so they can get it back on another device.
+    pub async fn create_player(&self, name: Option<&str>) -> Result<NewPlayer, ClientError> {
+        self.create_player_with(&PlayerInput {
+            name: name.map(|name| name.to_string()),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Create a player, with the theme and locale of the random name if no name is given.
+    pub async fn create_player_with(&self, player: &PlayerInput) -> Result<NewPlayer, ClientError> {
+        self.post("api/v1/players", player).await
+    }
+
+    /// Get a player back from their recovery code, for example on a new device.
+    ///
+    /// The code can't be used again, the returned [`NewPlayer`] has the new one.
+    pub async fn recover_player(&self, recovery_code: &str) -> Result<NewPlayer, ClientError> {
+        self.post(
+            "api/v1/players/recover",
+            &RecoveryInput {
+                recovery_code: recovery_code.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Replace the recovery code of a player. The request is signed with the key of the player.
+    pub async fn new_recovery_code(&self, player: &Player) -> Result<NewPlayer, ClientError> {
+        self.post(
+            &format!("api/v1/players/{}/recovery_code", player.id),
+            &PlayerAuth::new_at(player, self.timestamp()),
+        )
+        .await
+    }
+
+    /// Merge `other` into `player`: the scores of `other` are moved to `player`, and `other` is
+    /// deleted. The request is signed with the keys of both players.
+    ///
+    /// Returns the player that is kept.
+    pub async fn merge_players(
+        &self,
+        player: &Player,
+        other: &Player,
+    ) -> Result<Player, ClientError> {
+        self.post(
+            &format!("api/v1/players/{}/merge", player.id),
+            &PlayerMerge::new_at(player, other, self.timestamp()),
+        )
+        .await
+    }
+
+    /// Change the name of a player. The update is signed with the key of the player.
+    ///
+    /// Returns the updated [`Player`].
+    pub async fn rename_player(&self, player: &Player, name: &str) -> Result<Player, ClientError> {
+        self.put(
+            &format!("api/v1/players/{}", player.id),
+            &PlayerUpdate::new_at(player, name.to_string(), self.timestamp()),
+        )
+        .await
+    }
+
+    /// Get the profile of a player, with their score history. The request is signed with the key
+    /// of the player.
+    pub async fn get_player_profile(
+        &self,
+        player: &Player,
+        query: &ScoreHistoryQuery,
+    ) -> Result<PlayerProfile, ClientError> {
+        let auth = PlayerAuth::new_at(player, self.timestamp());
+        let mut params = vec![
+            format!("timestamp={}", auth.timestamp),
+            format!("k={}", auth.k),
+        ];
+        if let Some(leaderboard) = query.leaderboard {
+            params.push(format!("leaderboard={}", leaderboard));
+        }
+        params.push(match query.order {
+            ScoreHistoryOrder::Recent => "order=recent".to_string(),
+            ScoreHistoryOrder::Best => "order=best".to_string(),
+        });
+        if let Some(limit) = query.limit {
+            params.push(format!("limit={}", limit));
+        }
+        if let Some(offset) = query.offset {
+            params.push(format!("offset={}", offset));
+        }
+        self.get(&with_params(
+            format!("api/v1/players/{}/profile", player.id),
+            params,
+        ))
+        .await
+    }
+
+    /// Get the time of the server.
+    pub async fn server_time(&self) -> Result<ServerTime, ClientError> {
+        self.get("api/v1/time").await
+    }
+
+    /// Get the server time to correct the timestamp of scores if the local clock is off, as
+    /// scores too far from the server time are rejected.
+    pub async fn sync_clock(&self) -> Result<(), ClientError> {
+        let time = self.server_time().await?;
+        self.clock_offset
+            .store(time.timestamp as i64 - now() as i64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Current time as a Unix timestamp in seconds, corrected with the server time if
+    /// [`Self::sync_clock`] was called.
+    pub fn timestamp(&self) -> u64 {
+        (now() as i64 + self.clock_offset.load(Ordering::Relaxed)) as u64
+    }
+
+    /// Sign a score for a leaderboard, with the current time.
+    ///
+    /// The score can be sent later with [`Self::submit_score`].
+    pub fn sign_score(
+        &self,
+        leaderboard_key: Uuid,
+        player: &Player,
+        score: f32,
+        meta: Option<String>,
+    ) -> ScoreInput {
+        ScoreInput::new_at(
+            score,
+            player.clone(),
+            meta,
+            leaderboard_key,
+            self.timestamp(),
+        )
+    }
+
+    /// Send a signed score to a leaderboard.
+    pub async fn submit_score(
+        &self,
+        leaderboard: Uuid,
+        score: &ScoreInput,
+    ) -> Result<(), ClientError> {
+        self.post(&format!("api/v1/scores/{}", leaderboard), score)
+            .await
+    }
+
+    /// Send a score signed earlier to a leaderboard, marked as sent now. The server accepts it
+    /// even if it was made longer ago than its time window, for example while offline.
+    ///
+    /// The score keeps its signature, so the server recognizes it if it was already saved.
+    pub async fn submit_delayed_score(
+        &self,
+        leaderboard: Uuid,
+        player: &Player,
+        score: &ScoreInput,
+    ) -> Result<(), ClientError> {
+        self.submit_score(leaderboard, &score.sent_at(player, self.timestamp()))
+            .await
+    }
+
+    /// Sign and send a score to a leaderboard.
+    ///
+    /// Metadata can be information about the game, victory conditions, ...
+    pub async fn send_score(
+        &self,
+        leaderboard: Uuid,
+        leaderboard_key: Uuid,
+        player: &Player,
+        score: f32,
+        meta: Option<String>,
+    ) -> Result<(), ClientError> {
+        let score = self.sign_score(leaderboard_key, player, score, meta);
+        self.submit_score(leaderboard, &score).await
+    }
+
+    /// Get the scores of a leaderboard, sorted following the sort order configured for the
+    /// leaderboard, best score first.
+    pub async fn get_scores(
+        &self,
+        leaderboard: Uuid,
+        query: &ScoresQuery,
+    ) -> Result<Vec<Score>, ClientError> {
+        let mut params = vec![];
+        if let Some(limit) = query.limit {
+            params.push(format!("limit={}", limit));
+        }
+        params.extend(query.window.as_params(query.utc_offset));
+        if let Some(player) = query.player.as_ref() {
+            params.extend(self.viewer_params(player));
+        }
+        self.get(&with_params(
+            format!("api/v1/scores/{}", leaderboard),
+            params,
+        ))
+        .await
+    }
+
+    /// Get the best rank of a player, with `count` scores above and below their best score. The
+    /// request is signed with the key of the player.
+    pub async fn get_player_ranking(
+        &self,
+        leaderboard: Uuid,
+        player: &Player,
+        count: u32,
+    ) -> Result<PlayerRanking, ClientError> {
+        let mut params = vec![format!("count={}", count)];
+        params.extend(self.viewer_params(player));
+        self.get(&with_params(
+            format!("api/v1/scores/{}/around/{}", leaderboard, player.id),
+            params,
+        ))
+        .await
+    }
+
+    /// Sign as the player viewing scores. Shadow banned players still see their own scores.
+    fn viewer_params(&self, player: &Player) -> Vec<String> {
+        let auth = PlayerAuth::new_at(player, self.timestamp());
+        vec![
+            format!("player={}", player.id),
+            format!("timestamp={}", auth.timestamp),
+            format!("k={}", auth.k),
+        ]
+    }
+}
+
+fn with_params(path: String, params: Vec<String>) -> String {
+    if params.is_empty() {
+        path
+    } else {
+        format!("{}?{}", path, params.join("&"))
+    }
+}
+
+/// Current time as a Unix timestamp in seconds.
+fn now() -> u64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs();
+    #[cfg(target_arch = "wasm32")]
+    let timestamp = (js_sys::Date::now() / 1000.0) as u64;
+    timestamp
+}
+
+/// Scores to get from a leaderboard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoresQuery {
+    /// Maximum number of scores. The server default is used if not set.
+    pub limit: Option<u32>,
+    /// Only get scores submitted during this window.
+    pub window: TimeWindow,
+    /// Offset from UTC, in minutes, of the timezone used to find the start of a day, week or
+    /// month for the [`TimeWindow`].
+    pub utc_offset: i32,
+    /// The player viewing the scores. The request is signed with their key.
+    pub player: Option<Player>,
+}
+
+/// Scores to get from the history of a player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScoreHistoryQuery {
+    /// Only get the scores of this leaderboard. Scores of all leaderboards are returned if not
+    /// set.
+    pub leaderboard: Option<Uuid>,
+    /// Order of the scores.
+    pub order: ScoreHistoryOrder,
+    /// Maximum number of scores. The server default is used if not set.
+    pub limit: Option<u32>,
+    /// Number of scores to skip, to get the following pages.
+    pub offset: Option<u32>,
+}
+
+/// Time window of the scores in the leaderboard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeWindow {
+    /// All the scores.
+    #[default]
+    AllTime,
+    /// Scores since the start of the current day.
+    Today,
+    /// Scores since the start of the current week, on Monday.
+    ThisWeek,
+    /// Scores since the start of the current month.
+    ThisMonth,
+    /// Scores between two Unix timestamps, in seconds.
+    Range {
+        /// Start of the range, inclusive.
+        since: Option<u64>,
+        /// End of the range, exclusive.
+        until: Option<u64>,
+    },
+}
+
+impl TimeWindow {
+    fn as_params(&self, utc_offset: i32) -> Vec<String> {
+        match self {
+            TimeWindow::AllTime => vec![],
+            TimeWindow::Today => vec![
+                "period=today".to_string(),
+                format!("utc_offset={}", utc_offset),
+            ],
+            TimeWindow::ThisWeek => vec![
+                "period=week".to_string(),
+                format!("utc_offset={}", utc_offset),
+            ],
+            TimeWindow::ThisMonth => vec![
+                "period=month".to_string(),
+                format!("utc_offset={}", utc_offset),
+            ],
+            TimeWindow::Range { since, until } => {
+                let mut params = vec![];
+                if let Some(since) = since {
+                    params.push(format!("since={}", since));
+                }
+                if let Some(until) = until {
+                    params.push(format!("until={}", until));
+                }
+                params
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+use jornet_protocol::ErrorBody;
+
+/// Error while talking to the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// The server could not be reached.
+    Unreachable,
+    /// The server refused the request. The `code` of the error is stable and can be matched on.
+    Api(ErrorBody),
+    /// The server replied with something unexpected.
+    InvalidResponse,
+}
+
+impl ClientError {
+    /// Get the code of the error if it was returned by the server.
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ClientError::Api(error) => Some(&error.code),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Unreachable => write!(f, "server unreachable"),
+            ClientError::Api(error) => write!(f, "{} ({})", error.message, error.code),
+            ClientError::InvalidResponse => write!(f, "invalid response"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
@@ -0,0 +1,29 @@
+#![warn(missing_docs)]
+
+//! ![Jornet logo](https://jornet.vleue.com/logo-200.png)
+//!
+//! Client for [Jornet](https://jornet.vleue.com), independent of any game engine.
+//! - create, rename, recover and merge players
+//! - save high scores
+//! - get a leaderboard
+//!
+//! Requests go through an [`HttpBackend`]. By default, [`ureq`](https://docs.rs/ureq) is used
+//! on native and `fetch` in the browser, another one can be given with
+//! [`JornetClient::with_backend`].
+
+mod backend;
+mod client;
+mod error;
+
+#[cfg(target_arch = "wasm32")]
+pub use backend::FetchBackend;
+#[cfg(all(feature = "ureq", not(target_arch = "wasm32")))]
+pub use backend::UreqBackend;
+pub use backend::{BoxFuture, HttpBackend, HttpOptions, HttpRequest, HttpResponse, Method};
+pub use client::{JornetClient, ScoreHistoryQuery, ScoresQuery, TimeWindow, DEFAULT_HOST};
+pub use error::ClientError;
+pub use jornet_protocol::{
+    ErrorBody, NewPlayer, Player, PlayerInput, PlayerMerge, PlayerProfile, PlayerRanking,
+    PlayerScore, PlayerUpdate, RecoveryInput, Score, ScoreHistoryOrder, ScoreInput, ScoreSent,
+    ServerTime,
+};
@@ -0,0 +1,384 @@
+use std::sync::{Arc, Mutex};
+
+use futures_lite::future::block_on;
+use jornet_client::{
+    BoxFuture, ClientError, ErrorBody, HttpBackend, HttpRequest, HttpResponse, JornetClient,
+    Method, NewPlayer, Player, PlayerMerge, PlayerUpdate, ScoreHistoryOrder, ScoreHistoryQuery,
+    ScoreInput, ScoresQuery, TimeWindow,
+};
+use jornet_protocol::{PlayerAuth, SignatureVersion};
+use uuid::Uuid;
+
+/// Backend replying with the same response to every request, and keeping the requests.
+#[derive(Clone)]
+struct MockBackend {
+    response: Result<HttpResponse, ClientError>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl MockBackend {
+    fn new(status: u16, body: &str) -> Self {
+        Self {
+            response: Ok(HttpResponse {
+                status,
+                body: body.to_string(),
+            }),
+            requests: Default::default(),
+        }
+    }
+
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpBackend for MockBackend {
+    fn request(&self, request: HttpRequest) -> BoxFuture<Result<HttpResponse, ClientError>> {
+        self.requests.lock().unwrap().push(request);
+        let response = self.response.clone();
+        Box::pin(async move { response })
+    }
+}
+
+fn player() -> Player {
+    Player {
+        id: Uuid::new_v4(),
+        name: "player".to_string(),
+        key: Uuid::new_v4(),
+    }
+}
+
+#[test]
+fn create_player() {
+    let player = player();
+    let backend = MockBackend::new(
+        200,
+        &format!(
+            r#"{{"id":"{}","name":"player","key":"{}","recovery_code":"ABCD-EFGH-JKMN"}}"#,
+            player.id, player.key
+        ),
+    );
+    let client = JornetClient::with_backend("http://localhost:8080/", backend.clone());
+
+    assert_eq!(
+        block_on(client.create_player(Some("player"))),
+        Ok(NewPlayer {
+            player,
+            recovery_code: "ABCD-EFGH-JKMN".to_string()
+        })
+    );
+    assert_eq!(
+        backend.requests(),
+        vec![HttpRequest {
+            method: Method::Post,
+            url: "http://localhost:8080/api/v1/players".to_string(),
+            body: Some(r#"{"name":"player"}"#.to_string()),
+        }]
+    );
+}
+
+#[test]
+fn recover_and_merge_players() {
+    let (player, other) = (player(), player());
+    let backend = MockBackend::new(
+        200,
+        &format!(
+            r#"{{"id":"{}","name":"player","key":"{}","recovery_code":"ABCD-EFGH-JKMN"}}"#,
+            player.id, player.key
+        ),
+    );
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+
+    let recovered = block_on(client.recover_player("wxyz-wxyz-wxyz")).unwrap();
+    assert_eq!(recovered.player, player);
+    assert_eq!(
+        block_on(client.merge_players(&player, &other)),
+        Ok(player.clone())
+    );
+
+    let requests = backend.requests();
+    assert_eq!(
+        requests[0],
+        HttpRequest {
+            method: Method::Post,
+            url: "http://localhost:8080/api/v1/players/recover".to_string(),
+            body: Some(r#"{"recovery_code":"wxyz-wxyz-wxyz"}"#.to_string()),
+        }
+    );
+    assert_eq!(
+        requests[1].url,
+        format!("http://localhost:8080/api/v1/players/{}/merge", player.id)
+    );
+    let sent: PlayerMerge = serde_json::from_str(requests[1].body.as_ref().unwrap()).unwrap();
+    assert_eq!(sent.other, other.id);
+    assert!(sent.verify(player.id, player.key, other.key));
+}
+
+#[test]
+fn rename_player() {
+    let player = player();
+    let backend = MockBackend::new(
+        200,
+        &format!(
+            r#"{{"id":"{}","name":"renamed","key":"{}"}}"#,
+            player.id, player.key
+        ),
+    );
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+
+    let renamed = block_on(client.rename_player(&player, "renamed")).unwrap();
+    assert_eq!(renamed.name, "renamed");
+
+    let request = backend.requests().remove(0);
+    assert_eq!(request.method, Method::Put);
+    assert_eq!(
+        request.url,
+        format!("http://localhost:8080/api/v1/players/{}", player.id)
+    );
+    let sent: PlayerUpdate = serde_json::from_str(&request.body.unwrap()).unwrap();
+    assert_eq!(sent.name, "renamed");
+    assert!(sent.verify(player.id, player.key));
+}
+
+#[test]
+fn get_signed_player_profile() {
+    let player = player();
+    let backend = MockBackend::new(
+        200,
+        &format!(
+            r#"{{"id":"{}","name":"player","created_at":"2022-08-08T10:00:00Z","scores":[]}}"#,
+            player.id
+        ),
+    );
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+    let leaderboard = Uuid::new_v4();
+
+    let profile = block_on(client.get_player_profile(
+        &player,
+        &ScoreHistoryQuery {
+            leaderboard: Some(leaderboard),
+            order: ScoreHistoryOrder::Best,
+            limit: Some(5),
+            offset: Some(10),
+        },
+    ))
+    .unwrap();
+    assert_eq!(profile.id, player.id);
+
+    let request = backend.requests().remove(0);
+    assert_eq!(request.method, Method::Get);
+    let (path, query) = request.url.split_once('?').unwrap();
+    assert_eq!(
+        path,
+        format!("http://localhost:8080/api/v1/players/{}/profile", player.id)
+    );
+    let params = query.split('&').collect::<Vec<_>>();
+    let timestamp = params[0].strip_prefix("timestamp=").unwrap();
+    let k = params[1].strip_prefix("k=").unwrap();
+    let auth = PlayerAuth {
+        timestamp: timestamp.parse().unwrap(),
+        k: k.to_string(),
+    };
+    assert!(auth.verify(player.id, player.key));
+    assert_eq!(
+        params[2..],
+        [
+            format!("leaderboard={}", leaderboard).as_str(),
+            "order=best",
+            "limit=5",
+            "offset=10"
+        ]
+    );
+}
+
+#[test]
+fn get_scores_with_query() {
+    let backend = MockBackend::new(200, "[]");
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+    let leaderboard = Uuid::new_v4();
+    let player = player();
+
+    let scores = block_on(client.get_scores(leaderboard, &ScoresQuery::default()));
+    assert_eq!(scores, Ok(vec![]));
+    let scores = block_on(client.get_scores(
+        leaderboard,
+        &ScoresQuery {
+            limit: Some(10),
+            window: TimeWindow::Today,
+            utc_offset: 120,
+            player: Some(player.clone()),
+        },
+    ));
+    assert_eq!(scores, Ok(vec![]));
+
+    let urls = backend
+        .requests()
+        .into_iter()
+        .map(|request| request.url)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        urls[0],
+        format!("http://localhost:8080/api/v1/scores/{}", leaderboard)
+    );
+    let (path, query) = urls[1].split_once('?').unwrap();
+    assert_eq!(
+        path,
+        format!("http://localhost:8080/api/v1/scores/{}", leaderboard)
+    );
+    let params = query.split('&').collect::<Vec<_>>();
+    assert_eq!(
+        params[..4],
+        [
+            "limit=10",
+            "period=today",
+            "utc_offset=120",
+            format!("player={}", player.id).as_str()
+        ]
+    );
+    assert_viewer(&params[4..], &player);
+}
+
+#[test]
+fn get_player_ranking_signed() {
+    let backend = MockBackend::new(200, r#"{"rank":1,"scores":[]}"#);
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+    let leaderboard = Uuid::new_v4();
+    let player = player();
+
+    let ranking = block_on(client.get_player_ranking(leaderboard, &player, 3)).unwrap();
+    assert_eq!(ranking.rank, 1);
+
+    let request = backend.requests().remove(0);
+    let (path, query) = request.url.split_once('?').unwrap();
+    assert_eq!(
+        path,
+        format!(
+            "http://localhost:8080/api/v1/scores/{}/around/{}",
+            leaderboard, player.id
+        )
+    );
+    let params = query.split('&').collect::<Vec<_>>();
+    assert_eq!(
+        params[..2],
+        ["count=3", format!("player={}", player.id).as_str()]
+    );
+    assert_viewer(&params[2..], &player);
+}
+
+/// Check that the `timestamp` and `k` query parameters are a valid [`PlayerAuth`].
+fn assert_viewer(params: &[&str], player: &Player) {
+    let auth = PlayerAuth {
+        timestamp: params[0]
+            .strip_prefix("timestamp=")
+            .unwrap()
+            .parse()
+            .unwrap(),
+        k: params[1].strip_prefix("k=").unwrap().to_string(),
+    };
+    assert!(auth.verify(player.id, player.key));
+}
+
+#[test]
+fn send_signed_score() {
+    let backend = MockBackend::new(200, "null");
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+    let (leaderboard, key) = (Uuid::new_v4(), Uuid::new_v4());
+    let player = player();
+
+    assert_eq!(
+        block_on(client.send_score(leaderboard, key, &player, 12.5, None)),
+        Ok(())
+    );
+
+    let request = backend.requests().remove(0);
+    assert_eq!(
+        request.url,
+        format!("http://localhost:8080/api/v1/scores/{}", leaderboard)
+    );
+    let sent: ScoreInput = serde_json::from_str(&request.body.unwrap()).unwrap();
+    assert_eq!(sent.version, SignatureVersion::CURRENT.as_u8());
+    assert!(sent.verify(SignatureVersion::CURRENT, player.key, key));
+}
+
+#[test]
+fn submit_delayed_score() {
+    let backend = MockBackend::new(200, "null");
+    let client = JornetClient::with_backend("http://localhost:8080", backend.clone());
+    let player = player();
+    let key = Uuid::new_v4();
+    let score = ScoreInput::new_at(1.0, player.clone(), None, key, 1000);
+
+    assert_eq!(
+        block_on(client.submit_delayed_score(Uuid::new_v4(), &player, &score)),
+        Ok(())
+    );
+
+    let request = backend.requests().remove(0);
+    let sent: ScoreInput = serde_json::from_str(&request.body.unwrap()).unwrap();
+    // the score is unchanged, with the time it was sent at
+    assert_eq!(sent.k, score.k);
+    assert_eq!(sent.timestamp, 1000);
+    assert!(sent.verify_sent(player.key));
+    assert!(sent.sent.unwrap().timestamp.abs_diff(client.timestamp()) <= 1);
+}
+
+#[test]
+fn correct_clock_with_server_time() {
+    let client = JornetClient::with_backend(
+        "http://localhost:8080",
+        MockBackend::new(200, r#"{"timestamp":1000,"score_time_window":300}"#),
+    );
+
+    assert_eq!(block_on(client.sync_clock()), Ok(()));
+    assert!(client.timestamp().abs_diff(1000) <= 1);
+    let score = client.sign_score(Uuid::new_v4(), &player(), 1.0, None);
+    assert!(score.timestamp.abs_diff(1000) <= 1);
+}
+
+#[test]
+fn typed_errors() {
+    let client = JornetClient::with_backend(
+        "http://localhost:8080",
+        MockBackend::new(
+            404,
+            r#"{"code":"unknown_player","message":"unknown player"}"#,
+        ),
+    );
+    let error = block_on(client.create_player(None)).unwrap_err();
+    assert_eq!(
+        error,
+        ClientError::Api(ErrorBody {
+            code: "unknown_player".to_string(),
+            message: "unknown player".to_string(),
+        })
+    );
+    assert_eq!(error.code(), Some("unknown_player"));
+
+    let client = JornetClient::with_backend(
+        "http://localhost:8080",
+        MockBackend::new(502, "<html>Bad Gateway</html>"),
+    );
+    assert_eq!(
+        block_on(client.create_player(None)),
+        Err(ClientError::InvalidResponse)
+    );
+
+    let client =
+        JornetClient::with_backend("http://localhost:8080", MockBackend::new(200, "not json"));
+    assert_eq!(
+        block_on(client.server_time()),
+        Err(ClientError::InvalidResponse)
+    );
+
+    let client = JornetClient::with_backend(
+        "http://localhost:8080",
+        MockBackend {
+            response: Err(ClientError::Unreachable),
+            requests: Default::default(),
+        },
+    );
+    assert_eq!(
+        block_on(client.server_time()),
+        Err(ClientError::Unreachable)
+    );
+}
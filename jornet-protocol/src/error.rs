@@ -0,0 +1,9 @@
+/// Body of an error response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ErrorBody {
+    /// Stable code of the error, that clients can match on.
+    pub code: String,
+    /// Description of the error.
+    pub message: String,
+}
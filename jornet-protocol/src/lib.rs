@@ -0,0 +1,26 @@
+#![warn(missing_docs)]
+
+//! ![Jornet logo](https://jornet.vleue.com/logo-200.png)
+//!
+//! Protocol shared between [Jornet](https://jornet.vleue.com) clients and server.
+//!
+//! With the `serde` feature, the types of the API can be serialized to and from JSON.
+
+mod error;
+mod player;
+mod profile;
+mod score;
+mod signature;
+mod time;
+
+pub use error::ErrorBody;
+pub use player::{
+    NewPlayer, Player, PlayerAuth, PlayerInput, PlayerMerge, PlayerUpdate, RecoveryInput,
+};
+pub use profile::{PlayerProfile, PlayerScore, ScoreHistoryOrder};
+pub use score::{PlayerRanking, Score, ScoreInput, ScoreSent};
+pub use signature::{
+    SignatureVersion, SignedPlayerAuth, SignedPlayerMerge, SignedPlayerUpdate, SignedScore,
+    SignedScoreSent,
+};
+pub use time::ServerTime;
@@ -0,0 +1,207 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use crate::{SignedPlayerAuth, SignedPlayerMerge, SignedPlayerUpdate};
+
+/// A player, with the key used to sign their scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Player {
+    /// ID of the player.
+    pub id: Uuid,
+    /// Name of the player.
+    pub name: String,
+    /// Key of the player. It must be kept secret.
+    pub key: Uuid,
+}
+
+/// A player that was just created or recovered, with the code to recover it on another device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NewPlayer {
+    /// The player.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub player: Player,
+    /// Code to get the player back with [`RecoveryInput`]. It can only be used once, a new one
+    /// is given when it's used. It must be kept secret.
+    pub recovery_code: String,
+}
+
+/// Request to recover a player from their recovery code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RecoveryInput {
+    /// The recovery code, as given in [`NewPlayer`]. Case and dashes are ignored.
+    pub recovery_code: String,
+}
+
+/// Request to create a player.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerInput {
+    /// Name of the player. A random one is generated if it's not set.
+    pub name: Option<String>,
+    /// Theme of the random name, like `animals` or `fantasy`. The server default is used if
+    /// it's not set or not available.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub theme: Option<String>,
+    /// Locale of the random name, like `fr` or `en-US`. The server default is used if it's not
+    /// set or not available.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub locale: Option<String>,
+}
+
+/// Signed request to update a player, proving ownership with their key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerUpdate {
+    /// New name of the player.
+    pub name: String,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Hex encoded signature of the update.
+    pub k: String,
+}
+
+impl PlayerUpdate {
+    /// Sign an update with the current time.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(player: &Player, name: String) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        Self::new_at(player, name, timestamp)
+    }
+
+    /// Sign an update with the given timestamp, in seconds since the Unix epoch.
+    pub fn new_at(player: &Player, name: String, timestamp: u64) -> Self {
+        let mut update = Self {
+            name,
+            timestamp,
+            k: String::new(),
+        };
+        update.k = update.signed(player.id).sign(player.key);
+        update
+    }
+
+    /// Get the fields of the update covered by its signature.
+    pub fn signed(&self, player: Uuid) -> SignedPlayerUpdate<'_> {
+        SignedPlayerUpdate {
+            player,
+            name: &self.name,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Check the signature of the update against the player.
+    pub fn verify(&self, player: Uuid, player_key: Uuid) -> bool {
+        self.signed(player).verify(player_key, &self.k)
+    }
+}
+
+/// Proof that a request is sent by a player, signed with their key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerAuth {
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Hex encoded signature of the authentication.
+    pub k: String,
+}
+
+impl PlayerAuth {
+    /// Sign an authentication with the current time.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(player: &Player) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        Self::new_at(player, timestamp)
+    }
+
+    /// Sign an authentication with the given timestamp, in seconds since the Unix epoch.
+    pub fn new_at(player: &Player, timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            k: SignedPlayerAuth {
+                player: player.id,
+                timestamp,
+            }
+            .sign(player.key),
+        }
+    }
+
+    /// Check the signature of the authentication against the player.
+    pub fn verify(&self, player: Uuid, player_key: Uuid) -> bool {
+        SignedPlayerAuth {
+            player,
+            timestamp: self.timestamp,
+        }
+        .verify(player_key, &self.k)
+    }
+}
+
+/// Request to merge a player into another one, signed with the keys of both players.
+///
+/// The scores of `other` are moved to the player the request is sent for, and `other` is
+/// deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerMerge {
+    /// ID of the player merged into the other one.
+    pub other: Uuid,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Hex encoded signature with the key of the player that is kept.
+    pub k: String,
+    /// Hex encoded signature with the key of the player merged into the other one.
+    pub other_k: String,
+}
+
+impl PlayerMerge {
+    /// Sign a merge of `other` into `player` with the current time.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(player: &Player, other: &Player) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        Self::new_at(player, other, timestamp)
+    }
+
+    /// Sign a merge of `other` into `player` with the given timestamp, in seconds since the
+    /// Unix epoch.
+    pub fn new_at(player: &Player, other: &Player, timestamp: u64) -> Self {
+        let signed = SignedPlayerMerge {
+            player: player.id,
+            other: other.id,
+            timestamp,
+        };
+        Self {
+            other: other.id,
+            timestamp,
+            k: signed.sign(player.key),
+            other_k: signed.sign(other.key),
+        }
+    }
+
+    /// Check the signatures of the merge against the keys of both players.
+    pub fn verify(&self, player: Uuid, player_key: Uuid, other_key: Uuid) -> bool {
+        let signed = SignedPlayerMerge {
+            player,
+            other: self.other,
+            timestamp: self.timestamp,
+        };
+        signed.verify(player_key, &self.k) && signed.verify(other_key, &self.other_k)
+    }
+}
@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+/// Public profile of a player, with their score history.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerProfile {
+    /// ID of the player.
+    pub id: Uuid,
+    /// Name of the player.
+    pub name: String,
+    /// Creation date of the player, in RFC 3339 format.
+    pub created_at: String,
+    /// Scores of the player, sorted following the requested [`ScoreHistoryOrder`].
+    pub scores: Vec<PlayerScore>,
+}
+
+/// A score from the history of a player.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerScore {
+    /// ID of the leaderboard the score was sent to.
+    pub leaderboard: Uuid,
+    /// The score.
+    pub score: f32,
+    /// Optional metadata.
+    pub meta: Option<String>,
+    /// Timestamp of the score, in RFC 3339 format.
+    pub timestamp: String,
+}
+
+/// Order of the scores in a [`PlayerProfile`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum ScoreHistoryOrder {
+    /// Most recent score first.
+    #[default]
+    Recent,
+    /// Best score first, following the sort order of each leaderboard. Scores are grouped by
+    /// leaderboard.
+    Best,
+}
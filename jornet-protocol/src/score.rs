@@ -0,0 +1,159 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use crate::{Player, SignatureVersion, SignedScore, SignedScoreSent};
+
+/// A score from a leaderboard.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Score {
+    /// The score.
+    pub score: f32,
+    /// Optional metadata.
+    pub meta: Option<String>,
+    /// Timestamp of the score, in RFC 3339 format.
+    pub timestamp: String,
+    /// The player name.
+    pub player: String,
+    /// Rank of the score in the leaderboard, starting at 1. Equal scores share the same rank.
+    pub rank: i64,
+}
+
+/// The best rank of a player in a leaderboard, with the scores surrounding it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlayerRanking {
+    /// Best rank of the player in the leaderboard.
+    pub rank: i64,
+    /// Scores around the best score of the player, including it.
+    pub scores: Vec<Score>,
+}
+
+/// A signed score, sent to a leaderboard.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ScoreInput {
+    /// The score.
+    pub score: f32,
+    /// ID of the player.
+    pub player: Uuid,
+    /// Optional metadata.
+    pub meta: Option<String>,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Hex encoded signature of the score.
+    pub k: String,
+    /// Version of the scheme used to sign the score, see [`SignatureVersion`]. Clients that
+    /// don't send it sign with [`SignatureVersion::V1`].
+    #[cfg_attr(feature = "serde", serde(default = "legacy_signature_version"))]
+    pub version: u8,
+    /// When the score was sent, if it was made earlier and waited to be sent, for example
+    /// because the player was offline. The score is then accepted as long as it was sent
+    /// recently.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub sent: Option<ScoreSent>,
+}
+
+/// Time a score was sent at, signed with the key of the player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ScoreSent {
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Hex encoded signature of the time, see [`SignedScoreSent`].
+    pub k: String,
+}
+
+#[cfg(feature = "serde")]
+fn legacy_signature_version() -> u8 {
+    SignatureVersion::V1.as_u8()
+}
+
+impl ScoreInput {
+    /// Sign a score with the current time.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(score: f32, player: Player, meta: Option<String>, leaderboard_key: Uuid) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        Self::new_at(score, player, meta, leaderboard_key, timestamp)
+    }
+
+    /// Sign a score with the given timestamp, in seconds since the Unix epoch.
+    pub fn new_at(
+        score: f32,
+        player: Player,
+        meta: Option<String>,
+        leaderboard_key: Uuid,
+        timestamp: u64,
+    ) -> Self {
+        let mut input = Self {
+            score,
+            player: player.id,
+            meta,
+            timestamp,
+            k: String::new(),
+            version: SignatureVersion::CURRENT.as_u8(),
+            sent: None,
+        };
+        input.k = input
+            .signed(leaderboard_key)
+            .sign(SignatureVersion::CURRENT, player.key);
+        input
+    }
+
+    /// Get the fields of the score covered by its signature.
+    pub fn signed(&self, leaderboard_key: Uuid) -> SignedScore<'_> {
+        SignedScore {
+            leaderboard_key,
+            player: self.player,
+            score: self.score,
+            meta: self.meta.as_deref(),
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Check the signature of the score against the key of the player.
+    pub fn verify(
+        &self,
+        version: SignatureVersion,
+        player_key: Uuid,
+        leaderboard_key: Uuid,
+    ) -> bool {
+        self.signed(leaderboard_key)
+            .verify(version, player_key, &self.k)
+    }
+
+    /// Get the score marked as sent at the given timestamp, in seconds since the Unix epoch.
+    /// The score itself and its signature are unchanged.
+    pub fn sent_at(&self, player: &Player, timestamp: u64) -> Self {
+        let k = SignedScoreSent {
+            score_k: &self.k,
+            timestamp,
+        }
+        .sign(player.key);
+        Self {
+            sent: Some(ScoreSent { timestamp, k }),
+            ..self.clone()
+        }
+    }
+
+    /// Check the signature of the time the score was sent at against the key of the player.
+    /// A score without a time it was sent at is valid.
+    pub fn verify_sent(&self, player_key: Uuid) -> bool {
+        match &self.sent {
+            Some(sent) => SignedScoreSent {
+                score_k: &self.k,
+                timestamp: sent.timestamp,
+            }
+            .verify(player_key, &sent.k),
+            None => true,
+        }
+    }
+}
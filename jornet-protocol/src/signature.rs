@@ -0,0 +1,275 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// Prefix of messages signed with [`SignatureVersion::V2`] and later.
+const TAG: &[u8] = b"jornet-score";
+/// Prefix of signed player updates.
+const PLAYER_UPDATE_TAG: &[u8] = b"jornet-player";
+/// Prefix of signed player authentications.
+const PLAYER_AUTH_TAG: &[u8] = b"jornet-auth";
+/// Prefix of signed player merges.
+const PLAYER_MERGE_TAG: &[u8] = b"jornet-merge";
+/// Prefix of signed times a score was sent at.
+const SCORE_SENT_TAG: &[u8] = b"jornet-sent";
+
+/// Version of the scheme used to sign a score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SignatureVersion {
+    /// Fields are concatenated as is. As `meta` is not length-prefixed, different scores can
+    /// share the same signature.
+    ///
+    /// Still accepted by the server while clients move to [`SignatureVersion::V2`].
+    V1,
+    /// Fields are preceded by a version tag, and `meta` by its presence and its length.
+    V2,
+}
+
+impl SignatureVersion {
+    /// Version used to sign new scores.
+    pub const CURRENT: SignatureVersion = SignatureVersion::V2;
+
+    /// Get the version number, as sent with a score.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            SignatureVersion::V1 => 1,
+            SignatureVersion::V2 => 2,
+        }
+    }
+
+    /// Get a version from its number, or `None` if it's not a known version.
+    pub fn from_u8(version: u8) -> Option<Self> {
+        match version {
+            1 => Some(SignatureVersion::V1),
+            2 => Some(SignatureVersion::V2),
+            _ => None,
+        }
+    }
+}
+
+/// Fields of a score covered by its signature.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedScore<'a> {
+    /// Key of the leaderboard the score is sent to.
+    pub leaderboard_key: Uuid,
+    /// ID of the player.
+    pub player: Uuid,
+    /// The score.
+    pub score: f32,
+    /// Optional metadata.
+    pub meta: Option<&'a str>,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+}
+
+impl SignedScore<'_> {
+    /// Get the bytes that are signed.
+    ///
+    /// With [`SignatureVersion::V2`], they are:
+    /// - the ASCII tag `jornet-score` followed by the version number as one byte
+    /// - the timestamp as 8 bytes little endian
+    /// - the leaderboard key and the player ID as 16 bytes each
+    /// - the score as 4 bytes little endian
+    /// - `0` if there is no metadata, or `1` followed by the length of the metadata in bytes
+    ///   as 8 bytes little endian then the UTF-8 metadata
+    pub fn message(&self, version: SignatureVersion) -> Vec<u8> {
+        let mut message = vec![];
+        if version >= SignatureVersion::V2 {
+            message.extend_from_slice(TAG);
+            message.push(version.as_u8());
+        }
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(self.leaderboard_key.as_bytes());
+        message.extend_from_slice(self.player.as_bytes());
+        message.extend_from_slice(&self.score.to_le_bytes());
+        match (version, self.meta) {
+            (SignatureVersion::V1, Some(meta)) => message.extend_from_slice(meta.as_bytes()),
+            (SignatureVersion::V1, None) => (),
+            (_, Some(meta)) => {
+                message.push(1);
+                message.extend_from_slice(&(meta.len() as u64).to_le_bytes());
+                message.extend_from_slice(meta.as_bytes());
+            }
+            (_, None) => message.push(0),
+        }
+        message
+    }
+
+    /// Sign the score with the key of the player. The signature is hex encoded.
+    pub fn sign(&self, version: SignatureVersion, player_key: Uuid) -> String {
+        sign(&self.message(version), player_key)
+    }
+
+    /// Check a hex encoded signature against the key of the player.
+    pub fn verify(&self, version: SignatureVersion, player_key: Uuid, signature: &str) -> bool {
+        verify(&self.message(version), player_key, signature)
+    }
+}
+
+/// Fields of a player update covered by its signature.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedPlayerUpdate<'a> {
+    /// ID of the player.
+    pub player: Uuid,
+    /// New name of the player.
+    pub name: &'a str,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+}
+
+impl SignedPlayerUpdate<'_> {
+    /// Get the bytes that are signed, following the layout of [`SignatureVersion::V2`]:
+    /// - the ASCII tag `jornet-player` followed by the version number as one byte
+    /// - the timestamp as 8 bytes little endian
+    /// - the player ID as 16 bytes
+    /// - the length of the name in bytes as 8 bytes little endian then the UTF-8 name
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = vec![];
+        message.extend_from_slice(PLAYER_UPDATE_TAG);
+        message.push(SignatureVersion::V2.as_u8());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(self.player.as_bytes());
+        message.extend_from_slice(&(self.name.len() as u64).to_le_bytes());
+        message.extend_from_slice(self.name.as_bytes());
+        message
+    }
+
+    /// Sign the update with the key of the player. The signature is hex encoded.
+    pub fn sign(&self, player_key: Uuid) -> String {
+        sign(&self.message(), player_key)
+    }
+
+    /// Check a hex encoded signature against the key of the player.
+    pub fn verify(&self, player_key: Uuid, signature: &str) -> bool {
+        verify(&self.message(), player_key, signature)
+    }
+}
+
+/// Fields of a player authentication covered by its signature.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedPlayerAuth {
+    /// ID of the player.
+    pub player: Uuid,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+}
+
+impl SignedPlayerAuth {
+    /// Get the bytes that are signed, following the layout of [`SignatureVersion::V2`]:
+    /// - the ASCII tag `jornet-auth` followed by the version number as one byte
+    /// - the timestamp as 8 bytes little endian
+    /// - the player ID as 16 bytes
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = vec![];
+        message.extend_from_slice(PLAYER_AUTH_TAG);
+        message.push(SignatureVersion::V2.as_u8());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(self.player.as_bytes());
+        message
+    }
+
+    /// Sign the authentication with the key of the player. The signature is hex encoded.
+    pub fn sign(&self, player_key: Uuid) -> String {
+        sign(&self.message(), player_key)
+    }
+
+    /// Check a hex encoded signature against the key of the player.
+    pub fn verify(&self, player_key: Uuid, signature: &str) -> bool {
+        verify(&self.message(), player_key, signature)
+    }
+}
+
+/// Fields of a player merge covered by its signatures. The same message is signed with the
+/// keys of both players.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedPlayerMerge {
+    /// ID of the player that is kept.
+    pub player: Uuid,
+    /// ID of the player merged into it.
+    pub other: Uuid,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+}
+
+impl SignedPlayerMerge {
+    /// Get the bytes that are signed, following the layout of [`SignatureVersion::V2`]:
+    /// - the ASCII tag `jornet-merge` followed by the version number as one byte
+    /// - the timestamp as 8 bytes little endian
+    /// - the ID of the player that is kept then of the player merged into it, as 16 bytes each
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = vec![];
+        message.extend_from_slice(PLAYER_MERGE_TAG);
+        message.push(SignatureVersion::V2.as_u8());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(self.player.as_bytes());
+        message.extend_from_slice(self.other.as_bytes());
+        message
+    }
+
+    /// Sign the merge with the key of one of the players. The signature is hex encoded.
+    pub fn sign(&self, player_key: Uuid) -> String {
+        sign(&self.message(), player_key)
+    }
+
+    /// Check a hex encoded signature against the key of one of the players.
+    pub fn verify(&self, player_key: Uuid, signature: &str) -> bool {
+        verify(&self.message(), player_key, signature)
+    }
+}
+
+/// Fields of the time a score was sent at covered by its signature.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedScoreSent<'a> {
+    /// Hex encoded signature of the score.
+    pub score_k: &'a str,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+}
+
+impl SignedScoreSent<'_> {
+    /// Get the bytes that are signed, following the layout of [`SignatureVersion::V2`]:
+    /// - the ASCII tag `jornet-sent` followed by the version number as one byte
+    /// - the timestamp as 8 bytes little endian
+    /// - the length of the signature of the score in bytes as 8 bytes little endian then the
+    ///   signature as sent, hex encoded
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = vec![];
+        message.extend_from_slice(SCORE_SENT_TAG);
+        message.push(SignatureVersion::V2.as_u8());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message.extend_from_slice(&(self.score_k.len() as u64).to_le_bytes());
+        message.extend_from_slice(self.score_k.as_bytes());
+        message
+    }
+
+    /// Sign the time with the key of the player. The signature is hex encoded.
+    pub fn sign(&self, player_key: Uuid) -> String {
+        sign(&self.message(), player_key)
+    }
+
+    /// Check a hex encoded signature against the key of the player.
+    pub fn verify(&self, player_key: Uuid, signature: &str) -> bool {
+        verify(&self.message(), player_key, signature)
+    }
+}
+
+fn mac(message: &[u8], player_key: Uuid) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(player_key.as_bytes())
+        .expect("HMAC can take a key of any size");
+    mac.update(message);
+    mac
+}
+
+fn sign(message: &[u8], player_key: Uuid) -> String {
+    hex::encode(&mac(message, player_key).finalize().into_bytes()[..])
+}
+
+fn verify(message: &[u8], player_key: Uuid, signature: &str) -> bool {
+    // a signature that is not valid hex can't be valid
+    match hex::decode(signature) {
+        Ok(signature) => mac(message, player_key)
+            .verify_slice(signature.as_slice())
+            .is_ok(),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,10 @@
+/// Time of the server, for clients to correct the timestamp of their scores if their clock is
+/// skewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ServerTime {
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// How far, in seconds, the timestamp of a score can be from the server time.
+    pub score_time_window: u64,
+}
@@ -0,0 +1,239 @@
+use jornet_protocol::{
+    ErrorBody, NewPlayer, Player, PlayerInput, PlayerProfile, PlayerRanking, PlayerScore,
+    PlayerUpdate, Score, ScoreHistoryOrder, ScoreInput, ScoreSent, ServerTime,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const PLAYER: Uuid = uuid::uuid!("a1b2c3d4-e5f6-4789-8abc-def012345678");
+const KEY: Uuid = uuid::uuid!("6c1b2a9e-3f4d-4e5a-8b7c-9d0e1f2a3b4c");
+
+/// Check that a value is sent as `wire`, and that `wire` is read back as the value. Changing
+/// the wire format of a type breaks games already shipped with an older version.
+fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(
+    value: T,
+    wire: Value,
+) {
+    assert_eq!(serde_json::to_value(&value).unwrap(), wire);
+    assert_eq!(serde_json::from_value::<T>(wire).unwrap(), value);
+}
+
+#[test]
+fn player() {
+    assert_round_trip(
+        Player {
+            id: PLAYER,
+            name: "player".to_string(),
+            key: KEY,
+        },
+        json!({
+            "id": "a1b2c3d4-e5f6-4789-8abc-def012345678",
+            "name": "player",
+            "key": "6c1b2a9e-3f4d-4e5a-8b7c-9d0e1f2a3b4c",
+        }),
+    );
+}
+
+#[test]
+fn new_player() {
+    // clients that only know about `Player` can still read it
+    assert_round_trip(
+        NewPlayer {
+            player: Player {
+                id: PLAYER,
+                name: "player".to_string(),
+                key: KEY,
+            },
+            recovery_code: "ABCD-EFGH-JKMN".to_string(),
+        },
+        json!({
+            "id": "a1b2c3d4-e5f6-4789-8abc-def012345678",
+            "name": "player",
+            "key": "6c1b2a9e-3f4d-4e5a-8b7c-9d0e1f2a3b4c",
+            "recovery_code": "ABCD-EFGH-JKMN",
+        }),
+    );
+}
+
+#[test]
+fn player_input() {
+    assert_round_trip(
+        PlayerInput {
+            name: Some("player".to_string()),
+            ..Default::default()
+        },
+        json!({ "name": "player" }),
+    );
+    assert_round_trip(PlayerInput::default(), json!({ "name": null }));
+    assert_round_trip(
+        PlayerInput {
+            name: None,
+            theme: Some("fantasy".to_string()),
+            locale: Some("fr".to_string()),
+        },
+        json!({ "name": null, "theme": "fantasy", "locale": "fr" }),
+    );
+}
+
+#[test]
+fn player_update() {
+    assert_round_trip(
+        PlayerUpdate {
+            name: "renamed".to_string(),
+            timestamp: 1_660_000_000,
+            k: "abcd".to_string(),
+        },
+        json!({
+            "name": "renamed",
+            "timestamp": 1_660_000_000,
+            "k": "abcd",
+        }),
+    );
+}
+
+#[test]
+fn score_input() {
+    assert_round_trip(
+        ScoreInput {
+            score: 10.5,
+            player: PLAYER,
+            meta: Some("level 3".to_string()),
+            timestamp: 1_660_000_000,
+            k: "abcd".to_string(),
+            version: 2,
+            sent: None,
+        },
+        json!({
+            "score": 10.5,
+            "player": "a1b2c3d4-e5f6-4789-8abc-def012345678",
+            "meta": "level 3",
+            "timestamp": 1_660_000_000,
+            "k": "abcd",
+            "version": 2,
+        }),
+    );
+}
+
+#[test]
+fn score_input_sent_later() {
+    assert_round_trip(
+        ScoreInput {
+            score: 10.5,
+            player: PLAYER,
+            meta: None,
+            timestamp: 1_660_000_000,
+            k: "abcd".to_string(),
+            version: 2,
+            sent: Some(ScoreSent {
+                timestamp: 1_660_003_600,
+                k: "ef01".to_string(),
+            }),
+        },
+        json!({
+            "score": 10.5,
+            "player": "a1b2c3d4-e5f6-4789-8abc-def012345678",
+            "meta": null,
+            "timestamp": 1_660_000_000,
+            "k": "abcd",
+            "version": 2,
+            "sent": { "timestamp": 1_660_003_600, "k": "ef01" },
+        }),
+    );
+}
+
+#[test]
+fn score_input_without_version_is_v1() {
+    let score: ScoreInput = serde_json::from_value(json!({
+        "score": 10.5,
+        "player": "a1b2c3d4-e5f6-4789-8abc-def012345678",
+        "meta": null,
+        "timestamp": 1_660_000_000,
+        "k": "abcd",
+    }))
+    .unwrap();
+    assert_eq!(score.version, 1);
+}
+
+#[test]
+fn player_ranking() {
+    let score = |rank, meta: Option<&str>| Score {
+        score: 10.5,
+        meta: meta.map(str::to_string),
+        timestamp: "2022-08-08T23:06:40Z".to_string(),
+        player: "player".to_string(),
+        rank,
+    };
+    let wire = |rank, meta: Value| {
+        json!({
+            "score": 10.5,
+            "meta": meta,
+            "timestamp": "2022-08-08T23:06:40Z",
+            "player": "player",
+            "rank": rank,
+        })
+    };
+    assert_round_trip(score(1, None), wire(1, Value::Null));
+    assert_round_trip(
+        PlayerRanking {
+            rank: 2,
+            scores: vec![score(1, Some("meta")), score(2, None)],
+        },
+        json!({
+            "rank": 2,
+            "scores": [wire(1, json!("meta")), wire(2, Value::Null)],
+        }),
+    );
+}
+
+#[test]
+fn server_time() {
+    assert_round_trip(
+        ServerTime {
+            timestamp: 1_660_000_000,
+            score_time_window: 300,
+        },
+        json!({ "timestamp": 1_660_000_000, "score_time_window": 300 }),
+    );
+}
+
+#[test]
+fn error_body() {
+    assert_round_trip(
+        ErrorBody {
+            code: "unknown_player".to_string(),
+            message: "unknown player".to_string(),
+        },
+        json!({ "code": "unknown_player", "message": "unknown player" }),
+    );
+}
+
+#[test]
+fn player_profile() {
+    assert_round_trip(
+        PlayerProfile {
+            id: PLAYER,
+            name: "player".to_string(),
+            created_at: "2022-08-08T10:00:00Z".to_string(),
+            scores: vec![PlayerScore {
+                leaderboard: KEY,
+                score: 12.5,
+                meta: None,
+                timestamp: "2022-08-09T10:00:00Z".to_string(),
+            }],
+        },
+        json!({
+            "id": "a1b2c3d4-e5f6-4789-8abc-def012345678",
+            "name": "player",
+            "created_at": "2022-08-08T10:00:00Z",
+            "scores": [{
+                "leaderboard": "6c1b2a9e-3f4d-4e5a-8b7c-9d0e1f2a3b4c",
+                "score": 12.5,
+                "meta": null,
+                "timestamp": "2022-08-09T10:00:00Z",
+            }],
+        }),
+    );
+    assert_round_trip(ScoreHistoryOrder::Recent, json!("recent"));
+    assert_round_trip(ScoreHistoryOrder::Best, json!("best"));
+}
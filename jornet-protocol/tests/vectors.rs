@@ -0,0 +1,246 @@
+use jornet_protocol::{
+    Player, PlayerAuth, PlayerMerge, PlayerUpdate, ScoreInput, ScoreSent, SignatureVersion,
+    SignedPlayerAuth, SignedPlayerMerge, SignedPlayerUpdate, SignedScore, SignedScoreSent,
+};
+use uuid::Uuid;
+
+const PLAYER_KEY: Uuid = uuid::uuid!("6c1b2a9e-3f4d-4e5a-8b7c-9d0e1f2a3b4c");
+const LEADERBOARD_KEY: Uuid = uuid::uuid!("0f9e8d7c-6b5a-4948-3726-150493827160");
+const PLAYER: Uuid = uuid::uuid!("a1b2c3d4-e5f6-4789-8abc-def012345678");
+const TIMESTAMP: u64 = 1_660_000_000;
+
+fn score(score: f32, meta: Option<&str>) -> SignedScore<'_> {
+    SignedScore {
+        leaderboard_key: LEADERBOARD_KEY,
+        player: PLAYER,
+        score,
+        meta,
+        timestamp: TIMESTAMP,
+    }
+}
+
+/// Signatures computed independently of this crate.
+const VECTORS: &[(SignatureVersion, f32, Option<&str>, &str)] = &[
+    (
+        SignatureVersion::V1,
+        543.21,
+        None,
+        "16b0d69d7fc0b4ccd8ae9fcb945d1ab6baa96f592f3cdca2164d620bcaa9f429",
+    ),
+    (
+        SignatureVersion::V1,
+        543.21,
+        Some(""),
+        "16b0d69d7fc0b4ccd8ae9fcb945d1ab6baa96f592f3cdca2164d620bcaa9f429",
+    ),
+    (
+        SignatureVersion::V1,
+        10.0,
+        Some("level 3"),
+        "b3e612d33b79f80165f280959d0545a01d2abb7285f12d785ecb2fba1fb02d7a",
+    ),
+    (
+        SignatureVersion::V2,
+        543.21,
+        None,
+        "a528cb3b983cd6b1e4a8bc51a78e7ce65c5cfded002e0cadc1657cfe0f1b9811",
+    ),
+    (
+        SignatureVersion::V2,
+        543.21,
+        Some(""),
+        "951853e06e40a7ca520f39332bfe74d63ec47d99cb532dd66356af9c0fa166b3",
+    ),
+    (
+        SignatureVersion::V2,
+        10.0,
+        Some("level 3"),
+        "b7c8133eafb366f279133ce923f4aaae0224830ae190f93b0df58f2c558f4de8",
+    ),
+];
+
+#[test]
+fn sign_test_vectors() {
+    for (version, value, meta, expected) in VECTORS {
+        assert_eq!(score(*value, *meta).sign(*version, PLAYER_KEY), *expected);
+    }
+}
+
+#[test]
+fn verify_test_vectors() {
+    for (version, value, meta, expected) in VECTORS {
+        assert!(score(*value, *meta).verify(*version, PLAYER_KEY, expected));
+        assert!(score(*value, *meta).verify(*version, PLAYER_KEY, &expected.to_uppercase()));
+        assert!(!score(*value + 1.0, *meta).verify(*version, PLAYER_KEY, expected));
+        assert!(!score(*value, *meta).verify(*version, LEADERBOARD_KEY, expected));
+    }
+}
+
+#[test]
+fn v2_distinguishes_missing_and_empty_meta() {
+    let missing = score(1.0, None).sign(SignatureVersion::V2, PLAYER_KEY);
+    let empty = score(1.0, Some("")).sign(SignatureVersion::V2, PLAYER_KEY);
+    assert_ne!(missing, empty);
+}
+
+#[test]
+fn versions_dont_share_signatures() {
+    let v1 = score(1.0, Some("meta")).sign(SignatureVersion::V1, PLAYER_KEY);
+    assert!(!score(1.0, Some("meta")).verify(SignatureVersion::V2, PLAYER_KEY, &v1));
+}
+
+#[test]
+fn reject_invalid_hex() {
+    assert!(!score(1.0, None).verify(SignatureVersion::V2, PLAYER_KEY, "not hex"));
+    assert!(!score(1.0, None).verify(SignatureVersion::V2, PLAYER_KEY, "abc"));
+    assert!(!score(1.0, None).verify(SignatureVersion::V2, PLAYER_KEY, ""));
+}
+
+#[test]
+fn player_update_test_vectors() {
+    // signatures computed independently of this crate
+    for (name, expected) in [
+        (
+            "Zany Wartortle",
+            "f10dbc7760eb18f3f191eba3bfd4c19bc59115f1bf2469e7f0330adc8620f023",
+        ),
+        (
+            "",
+            "387301e50677d6c28d8be47d09874f279fd82bdb78f33110d2048327bdb14378",
+        ),
+    ] {
+        let update = SignedPlayerUpdate {
+            player: PLAYER,
+            name,
+            timestamp: TIMESTAMP,
+        };
+        assert_eq!(update.sign(PLAYER_KEY), expected);
+        assert!(update.verify(PLAYER_KEY, expected));
+        assert!(!update.verify(LEADERBOARD_KEY, expected));
+    }
+}
+
+#[test]
+fn player_updates_dont_share_signatures_with_scores() {
+    let player = Player {
+        id: PLAYER,
+        name: "player".to_string(),
+        key: PLAYER_KEY,
+    };
+    let update = PlayerUpdate::new_at(&player, "renamed".to_string(), TIMESTAMP);
+    assert!(update.verify(PLAYER, PLAYER_KEY));
+    assert!(!update.verify(LEADERBOARD_KEY, PLAYER_KEY));
+    assert!(!score(1.0, None).verify(SignatureVersion::V2, PLAYER_KEY, &update.k));
+}
+
+#[test]
+fn player_auth_test_vector() {
+    // signature computed independently of this crate
+    let expected = "75231609214b5c3e13f9c659d81e266facdb08f8c7def0410baf6fafbb8596ea";
+    let auth = SignedPlayerAuth {
+        player: PLAYER,
+        timestamp: TIMESTAMP,
+    };
+    assert_eq!(auth.sign(PLAYER_KEY), expected);
+
+    let player = Player {
+        id: PLAYER,
+        name: "player".to_string(),
+        key: PLAYER_KEY,
+    };
+    let auth = PlayerAuth::new_at(&player, TIMESTAMP);
+    assert_eq!(auth.k, expected);
+    assert!(auth.verify(PLAYER, PLAYER_KEY));
+    assert!(!auth.verify(LEADERBOARD_KEY, PLAYER_KEY));
+    // an authentication can't be used as a player update
+    let update = PlayerUpdate {
+        name: String::new(),
+        timestamp: TIMESTAMP,
+        k: auth.k,
+    };
+    assert!(!update.verify(PLAYER, PLAYER_KEY));
+}
+
+#[test]
+fn player_merge_test_vector() {
+    // signature computed independently of this crate
+    let expected = "88e18a01b5663bf3ba898cde4f5f4e872371c236ac054aa611fef7f516008151";
+    let merge = SignedPlayerMerge {
+        player: PLAYER,
+        other: LEADERBOARD_KEY,
+        timestamp: TIMESTAMP,
+    };
+    assert_eq!(merge.sign(PLAYER_KEY), expected);
+
+    let player = Player {
+        id: PLAYER,
+        name: "player".to_string(),
+        key: PLAYER_KEY,
+    };
+    let other = Player {
+        id: LEADERBOARD_KEY,
+        name: "other".to_string(),
+        key: Uuid::nil(),
+    };
+    let merge = PlayerMerge::new_at(&player, &other, TIMESTAMP);
+    assert_eq!(merge.k, expected);
+    assert!(merge.verify(PLAYER, PLAYER_KEY, Uuid::nil()));
+    // both keys are needed
+    assert!(!merge.verify(PLAYER, PLAYER_KEY, PLAYER_KEY));
+    assert!(!merge.verify(PLAYER, Uuid::nil(), Uuid::nil()));
+    // and the merge can't be reversed
+    let reversed = PlayerMerge {
+        other: PLAYER,
+        ..merge
+    };
+    assert!(!reversed.verify(LEADERBOARD_KEY, Uuid::nil(), PLAYER_KEY));
+}
+
+#[test]
+fn score_sent_test_vector() {
+    // signature computed independently of this crate
+    let expected = "c781b4dad8a4de7c8249228cdf5333531330bc724244a2509826c9b5f9632186";
+    let sent = SignedScoreSent {
+        score_k: "abcd",
+        timestamp: TIMESTAMP + 60,
+    };
+    assert_eq!(sent.sign(PLAYER_KEY), expected);
+
+    let player = Player {
+        id: PLAYER,
+        name: "player".to_string(),
+        key: PLAYER_KEY,
+    };
+    let score = ScoreInput::new_at(1.0, player.clone(), None, LEADERBOARD_KEY, TIMESTAMP);
+    // a score that was not delayed has nothing more to check
+    assert!(score.verify_sent(PLAYER_KEY));
+
+    let sent = score.sent_at(&player, TIMESTAMP + 60);
+    assert_eq!(sent.k, score.k);
+    assert_eq!(sent.timestamp, score.timestamp);
+    assert!(sent.verify(SignatureVersion::V2, PLAYER_KEY, LEADERBOARD_KEY));
+    assert!(sent.verify_sent(PLAYER_KEY));
+    assert!(!sent.verify_sent(LEADERBOARD_KEY));
+    // the time can't be changed, or moved to another score
+    let mut moved = sent.clone();
+    moved.sent.as_mut().unwrap().timestamp += 1;
+    assert!(!moved.verify_sent(PLAYER_KEY));
+    let other = ScoreInput::new_at(2.0, player, None, LEADERBOARD_KEY, TIMESTAMP);
+    let moved = ScoreInput {
+        sent: sent.sent.clone(),
+        ..other
+    };
+    assert!(!moved.verify_sent(PLAYER_KEY));
+    // nor used as an authentication
+    let ScoreSent { timestamp, k } = sent.sent.unwrap();
+    assert!(!PlayerAuth { timestamp, k }.verify(PLAYER, PLAYER_KEY));
+}
+
+#[test]
+fn version_numbers() {
+    for version in [SignatureVersion::V1, SignatureVersion::V2] {
+        assert_eq!(SignatureVersion::from_u8(version.as_u8()), Some(version));
+    }
+    assert_eq!(SignatureVersion::from_u8(0), None);
+    assert_eq!(SignatureVersion::from_u8(3), None);
+}
@@ -63,6 +63,9 @@ type User = {
 type Leaderboard = {
     name: string,
     id: string,
+    sort_order: string,
+    score_mode: string,
+    season: number,
     scores: number,
     key?: string,
 }
@@ -76,6 +79,8 @@ type DashboardProps = {
 type DashboardState = {
     user?: User;
     new_leaderboard: string;
+    new_leaderboard_sort_order: string;
+    new_leaderboard_score_mode: string;
     leaderboards: Leaderboard[]
     new_leaderboard_data?: Leaderboard;
 };
@@ -85,6 +90,8 @@ class DashboardInner extends Component<DashboardProps, DashboardState> {
     state: DashboardState = {
         leaderboards: [],
         new_leaderboard: "",
+        new_leaderboard_sort_order: "desc",
+        new_leaderboard_score_mode: "all",
     };
     componentDidMount() {
         if (this.props.token === undefined) {
@@ -166,6 +173,21 @@ class DashboardInner extends Component<DashboardProps, DashboardState> {
                                     onChange={this.handleChangeNewLeaderboard}
                                 />
                             </FloatingLabel>
+                            <Form.Select
+                                value={this.state.new_leaderboard_sort_order}
+                                onChange={this.handleChangeNewLeaderboardSortOrder}
+                            >
+                                <option value="desc">Higher is better</option>
+                                <option value="asc">Lower is better</option>
+                            </Form.Select>
+                            <Form.Select
+                                value={this.state.new_leaderboard_score_mode}
+                                onChange={this.handleChangeNewLeaderboardScoreMode}
+                            >
+                                <option value="all">All scores</option>
+                                <option value="best">Best score per player</option>
+                                <option value="latest">Latest score per player</option>
+                            </Form.Select>
                             <Button
                                 variant="primary"
                                 onClick={this.handleSubmitNewLeaderboard}
@@ -215,6 +237,9 @@ class DashboardInner extends Component<DashboardProps, DashboardState> {
                             <thead>
                                 <tr>
                                     <th>Leaderboard</th>
+                                    <th>Order</th>
+                                    <th>Mode</th>
+                                    <th>Season</th>
                                     <th>Scores</th>
                                     <th>ID</th>
                                 </tr>
@@ -228,6 +253,9 @@ class DashboardInner extends Component<DashboardProps, DashboardState> {
                                                     <Nav.Link>{leaderboard.name}</Nav.Link>
                                                 </LinkContainer>
                                             </td>
+                                            <td>{leaderboard.sort_order === "asc" ? "Lower is better" : "Higher is better"}</td>
+                                            <td>{leaderboard.score_mode}</td>
+                                            <td>{leaderboard.season}</td>
                                             <td>{leaderboard.scores}</td>
                                             <td style={{ display: "flex" }}>
                                                 <p className="font-monospace">{leaderboard.id}</p>
@@ -246,6 +274,12 @@ class DashboardInner extends Component<DashboardProps, DashboardState> {
     handleChangeNewLeaderboard = (event: React.ChangeEvent<HTMLInputElement>) => {
         this.setState({ new_leaderboard: event.target.value });
     }
+    handleChangeNewLeaderboardSortOrder = (event: React.ChangeEvent<HTMLSelectElement>) => {
+        this.setState({ new_leaderboard_sort_order: event.target.value });
+    }
+    handleChangeNewLeaderboardScoreMode = (event: React.ChangeEvent<HTMLSelectElement>) => {
+        this.setState({ new_leaderboard_score_mode: event.target.value });
+    }
     handleSubmitNewLeaderboard = (event: React.FormEvent) => {
         this.setState({ new_leaderboard: "" });
         const requestOptions = {
@@ -254,7 +288,11 @@ class DashboardInner extends Component<DashboardProps, DashboardState> {
                 'Content-Type': 'application/json',
                 'Authorization': 'Bearer ' + this.props.token!
             },
-            body: JSON.stringify({ name: this.state.new_leaderboard })
+            body: JSON.stringify({
+                name: this.state.new_leaderboard,
+                sort_order: this.state.new_leaderboard_sort_order,
+                score_mode: this.state.new_leaderboard_score_mode,
+            })
         };
         fetch('/api/v1/leaderboards', requestOptions)
             .then(response => response.json())
@@ -8,6 +8,7 @@ type Score = {
     meta?: string,
     timestamp: string,
     player: string,
+    rank: number,
 }
 
 type LeaderboardProps = {
@@ -52,6 +53,7 @@ class LeaderboardInner extends PureComponent<LeaderboardProps, LeaderboardState>
                         <Table striped bordered hover>
                             <thead>
                                 <tr>
+                                    <th>Rank</th>
                                     <th>Score</th>
                                     <th>Player</th>
                                     <th>Timestamp</th>
@@ -62,6 +64,7 @@ class LeaderboardInner extends PureComponent<LeaderboardProps, LeaderboardState>
                                 {
                                     this.state.scores.map((score, index) => {
                                         return <tr key={index}>
+                                            <td>{score.rank}</td>
                                             <td>{score.score}</td>
                                             <td>{score.player}</td>
                                             <td>{score.timestamp}</td>
@@ -0,0 +1,4 @@
+ALTER TABLE leaderboards
+ADD sort_order TEXT NOT NULL
+DEFAULT 'desc'
+CHECK (sort_order IN ('asc', 'desc'));
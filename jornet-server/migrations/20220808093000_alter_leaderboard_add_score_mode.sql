@@ -0,0 +1,4 @@
+ALTER TABLE leaderboards
+ADD score_mode TEXT NOT NULL
+DEFAULT 'all'
+CHECK (score_mode IN ('all', 'best', 'latest'));
@@ -0,0 +1,23 @@
+ALTER TABLE leaderboards
+ADD season INT NOT NULL
+DEFAULT 1;
+ALTER TABLE scores
+ADD season INT NOT NULL
+DEFAULT 1;
+CREATE TABLE seasons(
+    leaderboard UUID NOT NULL,
+    season INT NOT NULL,
+    PRIMARY KEY (leaderboard, season),
+    ended_at TIMESTAMP NOT NULL
+);
+CREATE TABLE season_standings(
+    leaderboard UUID NOT NULL,
+    season INT NOT NULL,
+    rank BIGINT NOT NULL,
+    score REAL NOT NULL,
+    player UUID NOT NULL,
+    name TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL,
+    meta TEXT
+);
+CREATE INDEX season_standings_leaderboard_season ON season_standings (leaderboard, season);
@@ -0,0 +1,5 @@
+ALTER TABLE scores
+ADD submitted_at TIMESTAMP NOT NULL
+DEFAULT NOW();
+ALTER TABLE scores
+ADD deleted_at TIMESTAMP;
@@ -0,0 +1,9 @@
+CREATE TABLE player_bans(
+    leaderboard UUID NOT NULL,
+    player UUID NOT NULL,
+    PRIMARY KEY (leaderboard, player),
+    kind TEXT NOT NULL
+    CHECK (kind IN ('ban', 'shadow')),
+    created_at TIMESTAMP NOT NULL
+    DEFAULT NOW()
+);
@@ -0,0 +1,10 @@
+ALTER TABLE leaderboards
+ADD min_score REAL;
+ALTER TABLE leaderboards
+ADD max_score REAL;
+ALTER TABLE leaderboards
+ADD integer_only BOOLEAN NOT NULL
+DEFAULT FALSE;
+ALTER TABLE leaderboards
+ADD reject_non_finite BOOLEAN NOT NULL
+DEFAULT TRUE;
@@ -0,0 +1,4 @@
+ALTER TABLE scores
+ADD signature BYTEA;
+CREATE UNIQUE INDEX scores_leaderboard_signature
+ON scores(leaderboard, signature);
@@ -0,0 +1,2 @@
+ALTER TABLE players
+ADD updated_at TIMESTAMP
@@ -0,0 +1 @@
+CREATE INDEX players_name ON players(name);
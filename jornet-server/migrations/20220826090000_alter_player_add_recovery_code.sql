@@ -0,0 +1,3 @@
+ALTER TABLE players
+ADD recovery_code BYTEA;
+CREATE UNIQUE INDEX players_recovery_code ON players(recovery_code);
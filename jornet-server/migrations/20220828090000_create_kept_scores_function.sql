@@ -0,0 +1,33 @@
+-- Scores of a season that count in the ranking of a leaderboard. With the 'best' and 'latest'
+-- score modes, only one score is kept per player. Scores of shadow banned players are left out,
+-- except for the viewer's own.
+CREATE FUNCTION kept_scores(
+    for_leaderboard UUID,
+    for_season INT,
+    score_mode TEXT,
+    best_is_highest BOOLEAN,
+    since TIMESTAMP,
+    until TIMESTAMP,
+    viewer UUID
+)
+RETURNS TABLE(score REAL, meta TEXT, "timestamp" TIMESTAMP, player UUID, name TEXT)
+LANGUAGE SQL STABLE
+AS $$
+    SELECT DISTINCT ON (CASE WHEN score_mode = 'all' THEN scores.id ELSE scores.player END)
+        scores.score, scores.meta, scores.timestamp, scores.player, players.name
+    FROM scores JOIN players ON scores.player = players.id
+    WHERE scores.leaderboard = for_leaderboard AND scores.season = for_season
+        AND scores.deleted_at IS NULL
+        AND (since IS NULL OR scores.timestamp >= since)
+        AND (until IS NULL OR scores.timestamp < until)
+        AND (scores.player = viewer OR NOT EXISTS (
+            SELECT 1 FROM player_bans
+            WHERE player_bans.leaderboard = scores.leaderboard AND player_bans.player = scores.player
+                AND player_bans.kind = 'shadow'
+        ))
+    ORDER BY CASE WHEN score_mode = 'all' THEN scores.id ELSE scores.player END,
+        CASE WHEN score_mode = 'latest' THEN EXTRACT(EPOCH FROM scores.timestamp)::DOUBLE PRECISION
+            WHEN best_is_highest THEN scores.score::DOUBLE PRECISION
+            ELSE -scores.score::DOUBLE PRECISION END DESC,
+        scores.timestamp
+$$;
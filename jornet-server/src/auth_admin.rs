@@ -1,17 +1,20 @@
 use actix_web::{dev::ServiceRequest, web, Error, HttpMessage};
-use actix_web_httpauth::extractors::{
-    bearer::{BearerAuth, Config},
-    AuthenticationError,
-};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
 use biscuit_auth::{Biscuit, KeyPair};
 
-use crate::domains::admin::{AdminAccount, BiscuitFact};
+use crate::{
+    domains::admin::{AdminAccount, BiscuitFact},
+    error::ApiError,
+};
 
 pub async fn validator(
     req: ServiceRequest,
     credentials: BearerAuth,
 ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
-    let root = req.app_data::<web::Data<KeyPair>>().unwrap();
+    let root = match req.app_data::<web::Data<KeyPair>>() {
+        Some(root) => root,
+        None => return Err((ApiError::Internal.into(), req)),
+    };
     if let Some(user) = Biscuit::from_base64(credentials.token(), |_| root.public())
         .ok()
         .and_then(|biscuit| authorize(&biscuit))
@@ -19,7 +22,7 @@ pub async fn validator(
         req.extensions_mut().insert(user);
         Ok(req)
     } else {
-        Err((AuthenticationError::from(Config::default()).into(), req))
+        Err((ApiError::Unauthorized.into(), req))
     }
 }
 
@@ -8,6 +8,56 @@ pub struct Settings {
     pub application_port: u16,
     pub private_key: Option<String>,
     pub github_admin_app: OAuth,
+    /// How far, in seconds, the timestamp of a score or of a player update can be from the
+    /// server time.
+    pub score_time_window: u64,
+    /// How long, in seconds, a score can wait to be sent, for example while the player is
+    /// offline. The time it was sent at must still be within `score_time_window`.
+    pub score_max_delay: u64,
+    /// Accept scores signed with the first version of the signing scheme, until all clients
+    /// have moved to the current one.
+    pub accept_v1_signatures: bool,
+    pub player_names: PlayerNameSettings,
+    pub random_names: RandomNameSettings,
+}
+
+/// Rules for the names chosen by players.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PlayerNameSettings {
+    /// Maximum length of a name, in characters.
+    pub max_length: usize,
+    /// Characters allowed in a name on top of letters, digits and spaces.
+    pub allowed_symbols: String,
+    /// Only allow ASCII characters.
+    pub ascii_only: bool,
+    /// Words that can't be part of a name, one per line.
+    pub blocklist: String,
+}
+
+/// Names generated for players that didn't choose one.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RandomNameSettings {
+    /// Theme used when a player doesn't request one, or requests an unknown one.
+    pub default_theme: String,
+    /// Locale used when a player doesn't request one, or requests one missing from the theme.
+    pub default_locale: String,
+    /// Add a number after the name.
+    pub numeric_suffix: bool,
+    /// Themes added to the built-in ones, or replacing them.
+    pub themes: Vec<NameThemeSettings>,
+}
+
+/// Words of a theme of random names, in one locale.
+#[derive(Deserialize, Debug, Clone)]
+pub struct NameThemeSettings {
+    pub name: String,
+    pub locale: String,
+    /// Adjectives, one per line. If empty, the built-in adjectives of the locale are used.
+    pub adjectives: String,
+    /// Nouns, one per line.
+    pub nouns: String,
+    /// Put the adjective before the noun, as in English, or after it, as in French.
+    pub adjective_first: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -53,6 +103,6 @@ impl Settings {
             .and_then(|pk_string| base64::decode(pk_string).ok())
             .and_then(|pk_bytes| PrivateKey::from_bytes(&pk_bytes).ok())
             .map(KeyPair::from)
-            .unwrap_or_else(KeyPair::new)
+            .unwrap_or_default()
     }
 }
@@ -36,7 +36,7 @@ impl BiscuitFact for AdminAccount {
     fn from_authorizer(authorizer: &mut Authorizer) -> Option<Self> {
         let res: Vec<(String,)> = authorizer.query("data($id) <- user($id)").ok()?;
         Some(AdminAccount {
-            id: Uuid::parse_str(res.get(0)?.0.as_str()).ok()?,
+            id: Uuid::parse_str(res.first()?.0.as_str()).ok()?,
         })
     }
 }
@@ -103,11 +103,11 @@ impl AdminAccount {
         .await
         .is_ok()
     }
-    pub fn create_biscuit(&self, root: &KeyPair) -> Biscuit {
+    pub fn create_biscuit(&self, root: &KeyPair) -> Option<Biscuit> {
         let mut builder = Biscuit::builder(root);
         builder
             .add_authority_fact(AdminAccount { id: self.id }.as_biscuit_fact())
-            .unwrap();
+            .ok()?;
 
         builder
             .add_authority_check(
@@ -115,13 +115,13 @@ impl AdminAccount {
                     r#"check if time($time), $time < {}"#,
                     (OffsetDateTime::now_utc() + Duration::seconds(TOKEN_TTL))
                         .format(&Rfc3339)
-                        .unwrap()
+                        .ok()?
                 )
                 .as_str(),
             )
-            .unwrap();
+            .ok()?;
 
-        builder.build().unwrap()
+        builder.build().ok()
     }
 }
 
@@ -1,17 +1,163 @@
-use actix_web::{dev::HttpServiceFactory, web, HttpResponse, Responder};
+use actix_web::{dev::HttpServiceFactory, web, HttpResponse};
 use actix_web_httpauth::middleware::HttpAuthentication;
 use biscuit_auth::KeyPair;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::auth_admin::validator;
+use crate::{auth_admin::validator, error::ApiError};
 
-use super::admin::AdminAccount;
+use super::{
+    admin::AdminAccount,
+    moderation,
+    score::{Score, ScoreQuery, ScoreRecord, TimeWindow},
+    season::close_season,
+};
 
-#[derive(Deserialize, Serialize)]
+/// Direction in which scores are ranked. With [`SortOrder::Descending`], the highest score is
+/// the best one. With [`SortOrder::Ascending`], the lowest one is, as for a speedrun.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[serde(rename = "asc")]
+    Ascending,
+    #[default]
+    #[serde(rename = "desc")]
+    Descending,
+}
+
+impl SortOrder {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "asc",
+            SortOrder::Descending => "desc",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "asc" => SortOrder::Ascending,
+            _ => SortOrder::Descending,
+        }
+    }
+}
+
+/// Which scores are shown in the leaderboard. All scores are always kept, this only changes
+/// the public view of the leaderboard.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreMode {
+    /// Every score submitted.
+    #[default]
+    All,
+    /// Only the best score of each player.
+    Best,
+    /// Only the latest score of each player.
+    Latest,
+}
+
+impl ScoreMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ScoreMode::All => "all",
+            ScoreMode::Best => "best",
+            ScoreMode::Latest => "latest",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "best" => ScoreMode::Best,
+            "latest" => ScoreMode::Latest,
+            _ => ScoreMode::All,
+        }
+    }
+}
+
+/// Rules a score must follow to be accepted in a leaderboard.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ScoreValidation {
+    /// Lowest score accepted, inclusive.
+    pub min: Option<f32>,
+    /// Highest score accepted, inclusive.
+    pub max: Option<f32>,
+    /// Only accept whole numbers.
+    pub integer_only: bool,
+    /// Reject NaN and infinite scores.
+    pub reject_non_finite: bool,
+}
+
+impl Default for ScoreValidation {
+    fn default() -> Self {
+        Self {
+            min: None,
+            max: None,
+            integer_only: false,
+            reject_non_finite: true,
+        }
+    }
+}
+
+/// Rule of [`ScoreValidation`] that rejected a score.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreRejection {
+    NotFinite,
+    BelowMin,
+    AboveMax,
+    NotInteger,
+}
+
+impl ScoreValidation {
+    pub fn check(&self, score: f32) -> Result<(), ScoreRejection> {
+        if !score.is_finite() {
+            return if self.reject_non_finite {
+                Err(ScoreRejection::NotFinite)
+            } else {
+                Ok(())
+            };
+        }
+        if self.min.is_some_and(|min| score < min) {
+            return Err(ScoreRejection::BelowMin);
+        }
+        if self.max.is_some_and(|max| score > max) {
+            return Err(ScoreRejection::AboveMax);
+        }
+        if self.integer_only && score.fract() != 0.0 {
+            return Err(ScoreRejection::NotInteger);
+        }
+        Ok(())
+    }
+    /// Check that some scores can be accepted, refusing rules with a minimum above the maximum.
+    fn validate(&self) -> Result<(), ApiError> {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min > max => Err(ApiError::InvalidRequest(
+                "the minimum score can't be above the maximum score".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
 pub struct LeaderboardInput {
     pub name: String,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    #[serde(default)]
+    pub score_mode: ScoreMode,
+    #[serde(default)]
+    pub validation: ScoreValidation,
+}
+
+/// Changes to apply to a leaderboard. Fields left empty are not changed.
+#[derive(Deserialize, Serialize, Default)]
+pub struct LeaderboardUpdate {
+    pub name: Option<String>,
+    pub sort_order: Option<SortOrder>,
+    pub score_mode: Option<ScoreMode>,
+    /// Replaces all the validation rules of the leaderboard.
+    pub validation: Option<ScoreValidation>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -19,12 +165,56 @@ pub struct Leaderboard {
     pub id: Uuid,
     pub key: Uuid,
     pub name: String,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    #[serde(default)]
+    pub score_mode: ScoreMode,
+    /// Current season of the leaderboard, starting at 1.
+    pub season: i32,
+    #[serde(default)]
+    pub validation: ScoreValidation,
+}
+
+/// A leaderboard as stored in the database.
+struct LeaderboardRow {
+    id: Uuid,
+    key: Uuid,
+    name: String,
+    sort_order: String,
+    score_mode: String,
+    season: i32,
+    min_score: Option<f32>,
+    max_score: Option<f32>,
+    integer_only: bool,
+    reject_non_finite: bool,
+}
+
+impl From<LeaderboardRow> for Leaderboard {
+    fn from(row: LeaderboardRow) -> Self {
+        Leaderboard {
+            id: row.id,
+            key: row.key,
+            name: row.name,
+            sort_order: SortOrder::from_db(&row.sort_order),
+            score_mode: ScoreMode::from_db(&row.score_mode),
+            season: row.season,
+            validation: ScoreValidation {
+                min: row.min_score,
+                max: row.max_score,
+                integer_only: row.integer_only,
+                reject_non_finite: row.reject_non_finite,
+            },
+        }
+    }
 }
 
 #[derive(Serialize, Debug)]
 struct LeaderboardWithScoreCount {
     id: Uuid,
     name: String,
+    sort_order: SortOrder,
+    score_mode: ScoreMode,
+    season: i32,
     scores: i64,
 }
 
@@ -32,24 +222,89 @@ async fn new_leaderboard(
     account: web::ReqData<AdminAccount>,
     connection: web::Data<PgPool>,
     leaderboard: web::Json<LeaderboardInput>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
+    leaderboard.validation.validate()?;
     let leaderboard = Leaderboard {
         name: leaderboard.name.clone(),
         id: Uuid::new_v4(),
         key: Uuid::new_v4(),
+        sort_order: leaderboard.sort_order,
+        score_mode: leaderboard.score_mode,
+        season: 1,
+        validation: leaderboard.validation.clone(),
     };
     if leaderboard.create(&connection, account.id).await {
-        HttpResponse::Ok().json(leaderboard)
+        Ok(HttpResponse::Ok().json(leaderboard))
     } else {
-        HttpResponse::InternalServerError().finish()
+        Err(ApiError::Internal)
+    }
+}
+
+async fn update_leaderboard(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+    update: web::Json<LeaderboardUpdate>,
+) -> Result<HttpResponse, ApiError> {
+    if let Some(validation) = update.validation.as_ref() {
+        validation.validate()?;
+    }
+    let leaderboard = Leaderboard::update(&connection, *leaderboard, account.id, &update)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    Ok(HttpResponse::Ok().json(leaderboard))
+}
+
+async fn rotate_leaderboard_key(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let leaderboard = Leaderboard::rotate_key(&connection, *leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    Ok(HttpResponse::Ok().json(leaderboard))
+}
+
+async fn delete_leaderboard(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    if Leaderboard::delete(&connection, *leaderboard, account.id).await? {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::UnknownLeaderboard)
     }
 }
 
 async fn get_leaderboards(
     account: web::ReqData<AdminAccount>,
     connection: web::Data<PgPool>,
-) -> impl Responder {
-    HttpResponse::Ok().json(Leaderboard::get_all(&connection, account.id).await)
+) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().json(Leaderboard::get_all(&connection, account.id).await?))
+}
+
+async fn get_leaderboard_scores(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+    query: web::Query<ScoreQuery>,
+    window: web::Query<TimeWindow>,
+) -> Result<HttpResponse, ApiError> {
+    let leaderboard = Leaderboard::get_owned(&connection, *leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    let scores = Score::get_all(
+        &connection,
+        &leaderboard,
+        &query,
+        &window,
+        ScoreMode::All,
+        None,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(scores))
 }
 
 pub(crate) fn leaderboard(kp: web::Data<KeyPair>) -> impl HttpServiceFactory {
@@ -58,43 +313,215 @@ pub(crate) fn leaderboard(kp: web::Data<KeyPair>) -> impl HttpServiceFactory {
         .wrap(HttpAuthentication::bearer(validator))
         .route("", web::post().to(new_leaderboard))
         .route("", web::get().to(get_leaderboards))
+        .route("{leaderboard_id}", web::put().to(update_leaderboard))
+        .route("{leaderboard_id}", web::delete().to(delete_leaderboard))
+        .route(
+            "{leaderboard_id}/key",
+            web::post().to(rotate_leaderboard_key),
+        )
+        .route(
+            "{leaderboard_id}/scores",
+            web::get().to(get_leaderboard_scores),
+        )
+        .route("{leaderboard_id}/seasons", web::post().to(close_season))
+        .route(
+            "{leaderboard_id}/moderation/scores",
+            web::get().to(moderation::get_score_details),
+        )
+        .route(
+            "{leaderboard_id}/moderation/scores/{score_id}",
+            web::delete().to(moderation::delete_score),
+        )
+        .route(
+            "{leaderboard_id}/moderation/scores/{score_id}/restore",
+            web::post().to(moderation::restore_score),
+        )
+        .route(
+            "{leaderboard_id}/moderation/players/{player_id}",
+            web::delete().to(moderation::purge_player_scores),
+        )
+        .route(
+            "{leaderboard_id}/moderation/players/{player_id}/restore",
+            web::post().to(moderation::restore_player_scores),
+        )
+        .route(
+            "{leaderboard_id}/moderation/bans",
+            web::get().to(moderation::get_bans),
+        )
+        .route(
+            "{leaderboard_id}/moderation/bans/{player_id}",
+            web::put().to(moderation::ban_player),
+        )
+        .route(
+            "{leaderboard_id}/moderation/bans/{player_id}",
+            web::delete().to(moderation::unban_player),
+        )
 }
 
 impl Leaderboard {
-    async fn get_all(connection: &PgPool, owner: Uuid) -> Vec<LeaderboardWithScoreCount> {
-        sqlx::query!(
-            "SELECT leaderboards.id, name, count(scores.leaderboard) FROM leaderboards LEFT JOIN scores ON leaderboards.id = scores.leaderboard WHERE owner = $1 GROUP BY leaderboards.id;",
+    async fn get_all(
+        connection: &PgPool,
+        owner: Uuid,
+    ) -> Result<Vec<LeaderboardWithScoreCount>, ApiError> {
+        Ok(sqlx::query!(
+            "SELECT leaderboards.id, name, sort_order, score_mode, leaderboards.season, count(scores.leaderboard) FROM leaderboards LEFT JOIN scores ON leaderboards.id = scores.leaderboard AND scores.deleted_at IS NULL WHERE owner = $1 GROUP BY leaderboards.id;",
             owner
         )
         .fetch_all(connection)
-        .await
-        .unwrap()
-        .iter()
+        .await?
+        .into_iter()
         .map(|r| LeaderboardWithScoreCount {
             id: r.id,
-            name: r.name.clone(),
-            scores: r.count.unwrap(),
+            name: r.name,
+            sort_order: SortOrder::from_db(&r.sort_order),
+            score_mode: ScoreMode::from_db(&r.score_mode),
+            season: r.season,
+            scores: r.count.unwrap_or(0),
         })
-        .collect()
+        .collect::<Vec<_>>())
+    }
+
+    pub async fn get(connection: &PgPool, id: Uuid) -> Result<Option<Leaderboard>, ApiError> {
+        Ok(sqlx::query_as!(
+            LeaderboardRow,
+            "SELECT id, key, name, sort_order, score_mode, season, min_score, max_score, integer_only, reject_non_finite FROM leaderboards WHERE id = $1;",
+            id
+        )
+        .fetch_optional(connection)
+        .await?
+        .map(Leaderboard::from))
+    }
+
+    pub async fn get_owned(
+        connection: &PgPool,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<Leaderboard>, ApiError> {
+        Ok(sqlx::query_as!(
+            LeaderboardRow,
+            "SELECT id, key, name, sort_order, score_mode, season, min_score, max_score, integer_only, reject_non_finite FROM leaderboards WHERE id = $1 AND owner = $2;",
+            id,
+            owner
+        )
+        .fetch_optional(connection)
+        .await?
+        .map(Leaderboard::from))
+    }
+
+    pub async fn update(
+        connection: &PgPool,
+        id: Uuid,
+        owner: Uuid,
+        update: &LeaderboardUpdate,
+    ) -> Result<Option<Leaderboard>, ApiError> {
+        Ok(sqlx::query_as!(
+            LeaderboardRow,
+            r#"
+            UPDATE leaderboards
+            SET name = COALESCE($3, name), sort_order = COALESCE($4, sort_order), score_mode = COALESCE($5, score_mode),
+                min_score = CASE WHEN $6 THEN $7::REAL ELSE min_score END,
+                max_score = CASE WHEN $6 THEN $8::REAL ELSE max_score END,
+                integer_only = COALESCE($9, integer_only),
+                reject_non_finite = COALESCE($10, reject_non_finite)
+            WHERE id = $1 AND owner = $2
+            RETURNING id, key, name, sort_order, score_mode, season, min_score, max_score, integer_only, reject_non_finite
+            "#,
+            id,
+            owner,
+            update.name,
+            update.sort_order.map(|sort_order| sort_order.as_str()),
+            update.score_mode.map(|score_mode| score_mode.as_str()),
+            update.validation.is_some(),
+            update.validation.as_ref().and_then(|validation| validation.min),
+            update.validation.as_ref().and_then(|validation| validation.max),
+            update.validation.as_ref().map(|validation| validation.integer_only),
+            update
+                .validation
+                .as_ref()
+                .map(|validation| validation.reject_non_finite),
+        )
+        .fetch_optional(connection)
+        .await?
+        .map(Leaderboard::from))
     }
 
-    pub async fn get_key(connection: &PgPool, id: Uuid) -> Option<Uuid> {
-        sqlx::query!("SELECT key FROM leaderboards WHERE id = $1;", id)
-            .fetch_one(connection)
-            .await
-            .map(|r| r.key)
-            .ok()
+    /// Replace the key of a leaderboard. Scores signed with the previous key will be rejected.
+    pub async fn rotate_key(
+        connection: &PgPool,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<Leaderboard>, ApiError> {
+        Ok(sqlx::query_as!(
+            LeaderboardRow,
+            r#"
+            UPDATE leaderboards SET key = $3 WHERE id = $1 AND owner = $2
+            RETURNING id, key, name, sort_order, score_mode, season, min_score, max_score, integer_only, reject_non_finite
+            "#,
+            id,
+            owner,
+            Uuid::new_v4(),
+        )
+        .fetch_optional(connection)
+        .await?
+        .map(Leaderboard::from))
+    }
+
+    /// Delete a leaderboard, with all its scores, seasons and bans. Returns `false` if the leaderboard
+    /// was not found.
+    pub async fn delete(connection: &PgPool, id: Uuid, owner: Uuid) -> Result<bool, ApiError> {
+        let mut transaction = connection.begin().await?;
+
+        let deleted = sqlx::query!(
+            "DELETE FROM leaderboards WHERE id = $1 AND owner = $2",
+            id,
+            owner
+        )
+        .execute(&mut transaction)
+        .await?
+        .rows_affected()
+            == 1;
+        if !deleted {
+            return Ok(false);
+        }
+
+        for query in [
+            sqlx::query!("DELETE FROM scores WHERE leaderboard = $1", id),
+            sqlx::query!("DELETE FROM seasons WHERE leaderboard = $1", id),
+            sqlx::query!("DELETE FROM season_standings WHERE leaderboard = $1", id),
+            sqlx::query!("DELETE FROM player_bans WHERE leaderboard = $1", id),
+        ] {
+            query.execute(&mut transaction).await?;
+        }
+
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn get_key(connection: &PgPool, id: Uuid) -> Result<Option<Uuid>, ApiError> {
+        Ok(
+            sqlx::query!("SELECT key FROM leaderboards WHERE id = $1;", id)
+                .fetch_optional(connection)
+                .await?
+                .map(|r| r.key),
+        )
     }
 
     pub async fn create(&self, connection: &PgPool, owner: Uuid) -> bool {
         sqlx::query!(
             r#"
-            INSERT INTO leaderboards (id, name, owner, key) VALUES ($1, $2, $3, $4)
+            INSERT INTO leaderboards (id, name, owner, key, sort_order, score_mode, min_score, max_score, integer_only, reject_non_finite)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
             self.id,
             self.name,
             owner,
             self.key,
+            self.sort_order.as_str(),
+            self.score_mode.as_str(),
+            self.validation.min,
+            self.validation.max,
+            self.validation.integer_only,
+            self.validation.reject_non_finite,
         )
         .execute(connection)
         .await
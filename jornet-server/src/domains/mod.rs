@@ -2,6 +2,9 @@ pub mod admin;
 pub mod config;
 pub mod healthcheck;
 pub mod leaderboard;
+pub mod moderation;
 pub mod oauth;
 pub mod player;
 pub mod score;
+pub mod season;
+pub mod time;
@@ -0,0 +1,377 @@
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+use super::{
+    admin::AdminAccount,
+    leaderboard::Leaderboard,
+    score::{format_timestamp, DEFAULT_LIMIT, MAX_LIMIT},
+};
+
+/// A score with everything needed to moderate it.
+#[derive(Deserialize, Serialize)]
+pub struct ScoreDetails {
+    pub id: Uuid,
+    pub score: f32,
+    pub meta: Option<String>,
+    /// Timestamp sent by the player with the score.
+    pub timestamp: String,
+    /// Timestamp of when the score was received by the server.
+    pub submitted_at: String,
+    /// Set if the score was deleted by an admin.
+    pub deleted_at: Option<String>,
+    pub player: Uuid,
+    pub name: String,
+    pub season: i32,
+}
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct ScoreDetailsQuery {
+    /// Only return scores from this player.
+    pub player: Option<Uuid>,
+    /// Only return deleted scores if `true`, or scores not deleted if `false`.
+    pub deleted: Option<bool>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// How a player is banned from a leaderboard.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BanKind {
+    /// Scores sent by the player are rejected.
+    Ban,
+    /// Scores sent by the player are accepted, but only visible to them.
+    Shadow,
+}
+
+impl BanKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BanKind::Ban => "ban",
+            BanKind::Shadow => "shadow",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "shadow" => BanKind::Shadow,
+            _ => BanKind::Ban,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BanInput {
+    pub kind: BanKind,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct PlayerBan {
+    pub player: Uuid,
+    pub name: String,
+    pub kind: BanKind,
+    pub created_at: String,
+}
+
+/// Number of scores changed by a moderation action.
+#[derive(Deserialize, Serialize)]
+pub struct ModeratedScores {
+    pub scores: u64,
+}
+
+pub(crate) async fn get_score_details(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+    query: web::Query<ScoreDetailsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let leaderboard = Leaderboard::get_owned(&connection, *leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    Ok(HttpResponse::Ok().json(ScoreDetails::get_all(&connection, &leaderboard, &query).await?))
+}
+
+pub(crate) async fn delete_score(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, score) = path.into_inner();
+    let leaderboard = Leaderboard::get_owned(&connection, leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    if ScoreDetails::set_deleted(&connection, &leaderboard, score, true).await? {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::UnknownScore)
+    }
+}
+
+pub(crate) async fn restore_score(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, score) = path.into_inner();
+    let leaderboard = Leaderboard::get_owned(&connection, leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    if ScoreDetails::set_deleted(&connection, &leaderboard, score, false).await? {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::UnknownScore)
+    }
+}
+
+pub(crate) async fn purge_player_scores(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, player) = path.into_inner();
+    let leaderboard = Leaderboard::get_owned(&connection, leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    let scores = ScoreDetails::set_player_deleted(&connection, &leaderboard, player, true).await?;
+    Ok(HttpResponse::Ok().json(ModeratedScores { scores }))
+}
+
+pub(crate) async fn restore_player_scores(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, player) = path.into_inner();
+    let leaderboard = Leaderboard::get_owned(&connection, leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    let scores = ScoreDetails::set_player_deleted(&connection, &leaderboard, player, false).await?;
+    Ok(HttpResponse::Ok().json(ModeratedScores { scores }))
+}
+
+pub(crate) async fn get_bans(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let leaderboard = Leaderboard::get_owned(&connection, *leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    Ok(HttpResponse::Ok().json(PlayerBan::get_all(&connection, &leaderboard).await?))
+}
+
+pub(crate) async fn ban_player(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    ban: web::Json<BanInput>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, player) = path.into_inner();
+    let leaderboard = Leaderboard::get_owned(&connection, leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    if PlayerBan::set(&connection, &leaderboard, player, ban.kind).await? {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::UnknownPlayer)
+    }
+}
+
+pub(crate) async fn unban_player(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, player) = path.into_inner();
+    let leaderboard = Leaderboard::get_owned(&connection, leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    if PlayerBan::remove(&connection, &leaderboard, player).await? {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::PlayerNotBanned)
+    }
+}
+
+impl ScoreDetails {
+    /// Get a page of the scores of a leaderboard, from all seasons, most recently submitted
+    /// first.
+    pub async fn get_all(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        query: &ScoreDetailsQuery,
+    ) -> Result<Vec<ScoreDetails>, ApiError> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let offset = query.offset.unwrap_or(0).max(0);
+
+        sqlx::query!(
+            r#"
+            SELECT scores.id, scores.score, scores.meta, scores.timestamp, scores.submitted_at,
+                scores.deleted_at, scores.player, players.name, scores.season
+            FROM scores JOIN players ON scores.player = players.id
+            WHERE scores.leaderboard = $1
+                AND ($2::UUID IS NULL OR scores.player = $2)
+                AND ($3::BOOLEAN IS NULL OR (scores.deleted_at IS NOT NULL) = $3)
+            ORDER BY scores.submitted_at DESC, scores.id
+            LIMIT $4 OFFSET $5
+            "#,
+            leaderboard.id,
+            query.player,
+            query.deleted,
+            limit,
+            offset
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(|r| {
+            Ok(ScoreDetails {
+                id: r.id,
+                score: r.score,
+                meta: r.meta,
+                timestamp: format_timestamp(r.timestamp)?,
+                submitted_at: format_timestamp(r.submitted_at)?,
+                deleted_at: r.deleted_at.map(format_timestamp).transpose()?,
+                player: r.player,
+                name: r.name,
+                season: r.season,
+            })
+        })
+        .collect()
+    }
+
+    /// Soft delete or restore a score. Returns `false` if the score was not found in the
+    /// leaderboard.
+    pub async fn set_deleted(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        score: Uuid,
+        deleted: bool,
+    ) -> Result<bool, ApiError> {
+        Ok(sqlx::query!(
+            r#"
+            UPDATE scores SET deleted_at = CASE WHEN $3 THEN COALESCE(deleted_at, NOW()) END
+            WHERE id = $1 AND leaderboard = $2
+            "#,
+            score,
+            leaderboard.id,
+            deleted
+        )
+        .execute(connection)
+        .await?
+        .rows_affected()
+            == 1)
+    }
+
+    /// Soft delete or restore all the scores of a player in a leaderboard. Returns the number of
+    /// scores changed.
+    pub async fn set_player_deleted(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        player: Uuid,
+        deleted: bool,
+    ) -> Result<u64, ApiError> {
+        Ok(sqlx::query!(
+            r#"
+            UPDATE scores SET deleted_at = CASE WHEN $3 THEN NOW() END
+            WHERE player = $1 AND leaderboard = $2 AND (deleted_at IS NULL) = $3
+            "#,
+            player,
+            leaderboard.id,
+            deleted
+        )
+        .execute(connection)
+        .await?
+        .rows_affected())
+    }
+}
+
+impl PlayerBan {
+    pub async fn get_all(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+    ) -> Result<Vec<PlayerBan>, ApiError> {
+        sqlx::query!(
+            r#"
+            SELECT player_bans.player, players.name, player_bans.kind, player_bans.created_at
+            FROM player_bans JOIN players ON player_bans.player = players.id
+            WHERE player_bans.leaderboard = $1
+            ORDER BY player_bans.created_at
+            "#,
+            leaderboard.id
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(|r| {
+            Ok(PlayerBan {
+                player: r.player,
+                name: r.name,
+                kind: BanKind::from_db(&r.kind),
+                created_at: format_timestamp(r.created_at)?,
+            })
+        })
+        .collect()
+    }
+
+    /// Get how a player is banned from a leaderboard, if they are.
+    pub async fn get_kind(
+        connection: &PgPool,
+        leaderboard: Uuid,
+        player: Uuid,
+    ) -> Result<Option<BanKind>, ApiError> {
+        Ok(sqlx::query!(
+            "SELECT kind FROM player_bans WHERE leaderboard = $1 AND player = $2",
+            leaderboard,
+            player
+        )
+        .fetch_optional(connection)
+        .await?
+        .map(|r| BanKind::from_db(&r.kind)))
+    }
+
+    /// Ban a player from a leaderboard, or change how they are banned. Returns `false` if the
+    /// player doesn't exist.
+    pub async fn set(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        player: Uuid,
+        kind: BanKind,
+    ) -> Result<bool, ApiError> {
+        Ok(sqlx::query!(
+            r#"
+            INSERT INTO player_bans (leaderboard, player, kind)
+            SELECT $1, id, $3 FROM players WHERE id = $2
+            ON CONFLICT (leaderboard, player) DO UPDATE SET kind = $3
+            "#,
+            leaderboard.id,
+            player,
+            kind.as_str()
+        )
+        .execute(connection)
+        .await?
+        .rows_affected()
+            == 1)
+    }
+
+    /// Lift the ban of a player. Returns `false` if the player was not banned.
+    pub async fn remove(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        player: Uuid,
+    ) -> Result<bool, ApiError> {
+        Ok(sqlx::query!(
+            "DELETE FROM player_bans WHERE leaderboard = $1 AND player = $2",
+            leaderboard.id,
+            player
+        )
+        .execute(connection)
+        .await?
+        .rows_affected()
+            == 1)
+    }
+}
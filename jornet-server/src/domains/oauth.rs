@@ -1,17 +1,14 @@
 use std::collections::HashMap;
 
-use actix_web::{web, HttpResponse, Responder, Scope};
-use biscuit_auth::{
-    builder::{Fact, Term},
-    Authorizer, Biscuit, KeyPair,
-};
+use actix_web::{web, HttpResponse, Scope};
+use biscuit_auth::{Biscuit, KeyPair};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::configuration::Settings;
+use crate::{configuration::Settings, error::ApiError};
 
-use super::admin::{AdminAccount, GithubUser};
+use super::admin::{AdminAccount, BiscuitFact, GithubUser};
 
 pub const TOKEN_TTL: i64 = 600;
 
@@ -20,24 +17,6 @@ pub struct TokenReply {
     pub token: String,
 }
 
-trait BiscuitFact: Sized {
-    fn as_biscuit_fact(&self) -> Fact;
-    fn from_authorizer(authorizer: &mut Authorizer) -> Option<Self>;
-}
-
-impl BiscuitFact for AdminAccount {
-    fn as_biscuit_fact(&self) -> Fact {
-        Fact::new("user".to_string(), vec![Term::Str(self.id.to_string())])
-    }
-
-    fn from_authorizer(authorizer: &mut Authorizer) -> Option<Self> {
-        let res: Vec<(String,)> = authorizer.query("data($id) <- user($id)").ok()?;
-        Some(AdminAccount {
-            id: Uuid::parse_str(res.get(0)?.0.as_str()).ok()?,
-        })
-    }
-}
-
 #[derive(Deserialize)]
 struct UuidInput {
     uuid: Uuid,
@@ -47,23 +26,25 @@ async fn by_uuid(
     root: web::Data<KeyPair>,
     connection: web::Data<PgPool>,
     uuid: web::Json<UuidInput>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let account = AdminAccount { id: uuid.uuid };
     match (
         account.exist(&connection).await,
         account.has_github(&connection).await,
     ) {
-        (_, Some(_)) => return HttpResponse::InternalServerError().finish(),
+        (_, Some(_)) => return Err(ApiError::GithubAccount),
         (false, _) => {
             account.create(&connection).await;
         }
         (true, _) => (),
     }
 
-    let biscuit = account.create_biscuit(root.as_ref());
-    HttpResponse::Ok().json(TokenReply {
-        token: biscuit.to_base64().unwrap(),
-    })
+    let biscuit = account
+        .create_biscuit(root.as_ref())
+        .ok_or(ApiError::Internal)?;
+    Ok(HttpResponse::Ok().json(TokenReply {
+        token: biscuit.to_base64().map_err(|_| ApiError::Internal)?,
+    }))
 }
 pub fn authorize(token: &Biscuit) -> Option<AdminAccount> {
     let mut authorizer = token.authorizer().ok()?;
@@ -90,7 +71,7 @@ async fn oauth_callback(
     config: web::Data<Settings>,
     connection: web::Data<PgPool>,
     root: web::Data<KeyPair>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let mut params = HashMap::new();
     params.insert("client_id", &config.github_admin_app.client_id);
     params.insert("client_secret", &config.github_admin_app.client_secret);
@@ -104,10 +85,10 @@ async fn oauth_callback(
         .header("Accept", "application/json")
         .send()
         .await
-        .unwrap()
+        .map_err(|_| ApiError::Github)?
         .json::<GithubOauthResponse>()
         .await
-        .unwrap()
+        .map_err(|_| ApiError::Github)?
         .access_token;
     let user = client
         .get("https://api.github.com/user")
@@ -115,24 +96,28 @@ async fn oauth_callback(
         .header("user-agent", "jornet")
         .send()
         .await
-        .unwrap()
+        .map_err(|_| ApiError::Github)?
         .json::<GithubUser>()
         .await
-        .unwrap();
+        .map_err(|_| ApiError::Github)?;
 
     let admin = if user.exist(&connection).await {
-        user.has_admin(&connection).await.unwrap()
+        user.has_admin(&connection)
+            .await
+            .ok_or(ApiError::Internal)?
     } else {
         let account = AdminAccount { id: Uuid::new_v4() };
-        account.create(&connection).await;
+        if !account.create(&connection).await {
+            return Err(ApiError::Internal);
+        }
         user.create(&account, &connection).await;
         account
     };
 
-    let biscuit = admin.create_biscuit(&root);
-    HttpResponse::Ok().json(TokenReply {
-        token: biscuit.to_base64().unwrap(),
-    })
+    let biscuit = admin.create_biscuit(&root).ok_or(ApiError::Internal)?;
+    Ok(HttpResponse::Ok().json(TokenReply {
+        token: biscuit.to_base64().map_err(|_| ApiError::Internal)?,
+    }))
 }
 
 pub(crate) fn oauth() -> Scope {
@@ -140,9 +125,3 @@ pub(crate) fn oauth() -> Scope {
         .route("by_uuid", web::post().to(by_uuid))
         .route("callback", web::get().to(oauth_callback))
 }
-
-#[derive(Serialize)]
-struct Identity<'a> {
-    admin: &'a AdminAccount,
-    github: Option<GithubUser>,
-}
@@ -1,38 +1,375 @@
 use actix_cors::Cors;
-use actix_web::{dev::HttpServiceFactory, web, HttpResponse, Responder};
+use actix_web::{dev::HttpServiceFactory, web, HttpResponse};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::random_name::random_name;
+use crate::{
+    configuration::Settings, error::ApiError, player_name::NameValidator,
+    random_name::NameGenerator, recovery_code,
+};
 
-#[derive(Serialize, Deserialize)]
-pub struct Player {
-    pub id: Uuid,
-    pub name: String,
-    pub key: Uuid,
-}
+use super::score::{format_timestamp, DEFAULT_LIMIT, MAX_LIMIT};
 
-#[derive(Deserialize, Serialize)]
-pub struct PlayerInput {
-    pub name: Option<String>,
-}
+pub use jornet_protocol::{
+    NewPlayer, Player, PlayerAuth, PlayerInput, PlayerMerge, PlayerProfile, PlayerScore,
+    PlayerUpdate, RecoveryInput, ScoreHistoryOrder,
+};
+
+/// Number of random names tried before adding a number to make the name unique.
+const RANDOM_NAME_ATTEMPTS: usize = 10;
 
 async fn create_player(
     connection: web::Data<PgPool>,
+    names: web::Data<NameValidator>,
+    random_names: web::Data<dyn NameGenerator>,
     player: web::Json<PlayerInput>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
+    let name = match player.name.as_deref() {
+        Some(name) => names.validate(name).map_err(ApiError::InvalidName)?,
+        None => {
+            return Ok(HttpResponse::Ok().json(
+                create_with_random_name(&player, &names, &**random_names, &connection).await?,
+            ))
+        }
+    };
     let player = Player {
-        name: player.name.clone().unwrap_or_else(random_name),
+        name,
         id: Uuid::new_v4(),
         key: Uuid::new_v4(),
     };
+    let recovery_code = recovery_code::generate();
 
-    if player.save(&connection).await {
-        HttpResponse::Ok().json(player)
+    if player.save(&recovery_code, &connection).await {
+        Ok(HttpResponse::Ok().json(NewPlayer {
+            player,
+            recovery_code,
+        }))
     } else {
-        HttpResponse::InternalServerError().finish()
+        Err(ApiError::Internal)
+    }
+}
+
+/// Create a player with a random name that no other player has. Generated names go through
+/// the same validation as names chosen by players.
+async fn create_with_random_name(
+    input: &PlayerInput,
+    names: &NameValidator,
+    random_names: &dyn NameGenerator,
+    connection: &PgPool,
+) -> Result<NewPlayer, ApiError> {
+    let mut player = Player {
+        name: String::new(),
+        id: Uuid::new_v4(),
+        key: Uuid::new_v4(),
+    };
+    let recovery_code = recovery_code::generate();
+    for attempt in 0..RANDOM_NAME_ATTEMPTS * 2 {
+        let mut name = random_names.generate(input.theme.as_deref(), input.locale.as_deref());
+        if attempt >= RANDOM_NAME_ATTEMPTS {
+            // the theme may be too small for the number of players. A number already added by
+            // the generator is replaced rather than followed by another one
+            let base = match name.rsplit_once(' ') {
+                Some((base, number)) if number.chars().all(|c| c.is_ascii_digit()) => base,
+                _ => name.as_str(),
+            };
+            name = format!("{} {}", base, rand::thread_rng().gen_range(1000..10000));
+        }
+        player.name = match names.validate(&name) {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+
+        let mut transaction = connection.begin().await?;
+        lock_name(&player.name, &mut transaction).await?;
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO players (id, name, key, recovery_code) SELECT $1, $2, $3, $4
+            WHERE NOT EXISTS (SELECT 1 FROM players WHERE name = $2)
+            "#,
+            player.id,
+            player.name,
+            player.key,
+            recovery_code::hash(&recovery_code),
+        )
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+        if inserted.rows_affected() == 1 {
+            return Ok(NewPlayer {
+                player,
+                recovery_code,
+            });
+        }
+    }
+    Err(ApiError::Internal)
+}
+
+/// Lock a name until the end of the transaction, so that checking that no player has it and
+/// giving it to a player can't be interleaved with another player getting it.
+async fn lock_name(
+    name: &str,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "SELECT 1 AS locked FROM pg_advisory_xact_lock(hashtext($1))",
+        name
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map(|_| ())
+}
+
+async fn update_player(
+    connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
+    names: web::Data<NameValidator>,
+    update: web::Json<PlayerUpdate>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let name = names
+        .validate(&update.name)
+        .map_err(ApiError::InvalidName)?;
+    let mut player = Player::get(*id, &connection)
+        .await?
+        .ok_or(ApiError::UnknownPlayer)?;
+    if !update.verify(player.id, player.key) {
+        return Err(ApiError::InvalidSignature);
+    }
+    if !is_fresh(update.timestamp, config.score_time_window) {
+        return Err(ApiError::TimestampOutOfWindow);
+    }
+
+    let mut transaction = connection.begin().await?;
+    lock_name(&name, &mut transaction).await?;
+    let updated = sqlx::query!(
+        r#"
+        UPDATE players SET name = $2, updated_at = TO_TIMESTAMP($3)
+        WHERE id = $1 AND (updated_at IS NULL OR updated_at < TO_TIMESTAMP($3))
+        "#,
+        player.id,
+        name,
+        update.timestamp as f64,
+    )
+    .execute(&mut transaction)
+    .await?;
+    transaction.commit().await?;
+    if updated.rows_affected() == 0 {
+        // an update signed at the same time or later was already applied, this one could be
+        // a replay
+        return Err(ApiError::StalePlayerUpdate);
+    }
+    player.name = name;
+    Ok(HttpResponse::Ok().json(player))
+}
+
+async fn recover_player(
+    connection: web::Data<PgPool>,
+    input: web::Json<RecoveryInput>,
+) -> Result<HttpResponse, ApiError> {
+    let hash = recovery_code::hash(&input.recovery_code).ok_or(ApiError::InvalidRecoveryCode)?;
+    let recovery_code = recovery_code::generate();
+
+    // the code is replaced as it's used, so that it can't be used again
+    let player = sqlx::query!(
+        r#"
+        UPDATE players SET recovery_code = $2
+        WHERE recovery_code = $1
+        RETURNING id, name, key
+        "#,
+        hash,
+        recovery_code::hash(&recovery_code),
+    )
+    .fetch_optional(connection.get_ref())
+    .await?
+    .ok_or(ApiError::InvalidRecoveryCode)?;
+
+    Ok(HttpResponse::Ok().json(NewPlayer {
+        player: Player {
+            id: player.id,
+            name: player.name,
+            key: player.key,
+        },
+        recovery_code,
+    }))
+}
+
+/// Replace the recovery code of a player. Players created before recovery codes existed can get
+/// one this way.
+async fn new_recovery_code(
+    connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
+    auth: web::Json<PlayerAuth>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let player = Player::get(*id, &connection)
+        .await?
+        .ok_or(ApiError::UnknownPlayer)?;
+    if !auth.verify(player.id, player.key) {
+        return Err(ApiError::InvalidSignature);
+    }
+    if !is_fresh(auth.timestamp, config.score_time_window) {
+        return Err(ApiError::TimestampOutOfWindow);
+    }
+
+    let recovery_code = recovery_code::generate();
+    sqlx::query!(
+        "UPDATE players SET recovery_code = $2 WHERE id = $1",
+        player.id,
+        recovery_code::hash(&recovery_code),
+    )
+    .execute(connection.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(NewPlayer {
+        player,
+        recovery_code,
+    }))
+}
+
+/// Merge another player into this one: their scores and bans are moved to this player, and the
+/// other player is deleted. Standings of closed seasons are left as they were when the season
+/// closed.
+async fn merge_players(
+    connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
+    merge: web::Json<PlayerMerge>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    if merge.other == *id {
+        return Err(ApiError::InvalidRequest(
+            "can't merge a player into itself".to_string(),
+        ));
+    }
+    let player = Player::get(*id, &connection)
+        .await?
+        .ok_or(ApiError::UnknownPlayer)?;
+    let other = Player::get(merge.other, &connection)
+        .await?
+        .ok_or(ApiError::UnknownPlayer)?;
+    if !merge.verify(player.id, player.key, other.key) {
+        return Err(ApiError::InvalidSignature);
     }
+    if !is_fresh(merge.timestamp, config.score_time_window) {
+        return Err(ApiError::TimestampOutOfWindow);
+    }
+
+    let mut transaction = connection.begin().await?;
+    sqlx::query!(
+        "UPDATE scores SET player = $1 WHERE player = $2",
+        player.id,
+        other.id
+    )
+    .execute(&mut transaction)
+    .await?;
+    // a ban of either player applies to the merged player, the strongest one is kept
+    sqlx::query!(
+        r#"
+        INSERT INTO player_bans (leaderboard, player, kind, created_at)
+        SELECT leaderboard, $1, kind, created_at FROM player_bans WHERE player = $2
+        ON CONFLICT (leaderboard, player) DO UPDATE SET kind = CASE
+            WHEN EXCLUDED.kind = 'ban' THEN 'ban' ELSE player_bans.kind
+        END
+        "#,
+        player.id,
+        other.id
+    )
+    .execute(&mut transaction)
+    .await?;
+    sqlx::query!("DELETE FROM player_bans WHERE player = $1", other.id)
+        .execute(&mut transaction)
+        .await?;
+    sqlx::query!("DELETE FROM players WHERE id = $1", other.id)
+        .execute(&mut transaction)
+        .await?;
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok().json(player))
+}
+
+/// Check that a signed request was sent recently, to limit replays.
+pub(crate) fn is_fresh(timestamp: u64, window: u64) -> bool {
+    let now = OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+    timestamp.abs_diff(now) <= window
+}
+
+/// Query for the profile of a player, signed with their key as a [`PlayerAuth`].
+#[derive(Deserialize, Serialize)]
+pub struct ProfileQuery {
+    pub timestamp: u64,
+    pub k: String,
+    /// Only get the scores sent to this leaderboard.
+    pub leaderboard: Option<Uuid>,
+    #[serde(default)]
+    pub order: ScoreHistoryOrder,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+async fn get_profile(
+    connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
+    query: web::Query<ProfileQuery>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let auth = PlayerAuth {
+        timestamp: query.timestamp,
+        k: query.k.clone(),
+    };
+    let player = Player::get(*id, &connection)
+        .await?
+        .ok_or(ApiError::UnknownPlayer)?;
+    if !auth.verify(player.id, player.key) {
+        return Err(ApiError::InvalidSignature);
+    }
+    if !is_fresh(auth.timestamp, config.score_time_window) {
+        return Err(ApiError::TimestampOutOfWindow);
+    }
+
+    let created_at = sqlx::query!("SELECT created_at FROM players WHERE id = $1", player.id)
+        .fetch_one(connection.get_ref())
+        .await?
+        .created_at;
+    let scores = sqlx::query!(
+        r#"
+        SELECT scores.leaderboard, scores.score, scores.meta, scores.timestamp
+        FROM scores JOIN leaderboards ON scores.leaderboard = leaderboards.id
+        WHERE scores.player = $1 AND scores.deleted_at IS NULL
+            AND ($2::UUID IS NULL OR scores.leaderboard = $2)
+        ORDER BY CASE WHEN $3 THEN scores.leaderboard END,
+            CASE WHEN $3 THEN
+                CASE WHEN leaderboards.sort_order = 'asc' THEN -scores.score ELSE scores.score END
+            END DESC,
+            scores.timestamp DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        player.id,
+        query.leaderboard,
+        query.order == ScoreHistoryOrder::Best,
+        query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+        query.offset.unwrap_or(0).max(0),
+    )
+    .fetch_all(connection.get_ref())
+    .await?
+    .into_iter()
+    .map(|r| {
+        Ok(PlayerScore {
+            leaderboard: r.leaderboard,
+            score: r.score,
+            meta: r.meta,
+            timestamp: format_timestamp(r.timestamp)?,
+        })
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(HttpResponse::Ok().json(PlayerProfile {
+        id: player.id,
+        name: player.name,
+        created_at: format_timestamp(created_at)?,
+        scores,
+    }))
 }
 
 pub(crate) fn player() -> impl HttpServiceFactory {
@@ -45,37 +382,58 @@ pub(crate) fn player() -> impl HttpServiceFactory {
     web::scope("api/v1/players")
         .wrap(cors)
         .route("", web::post().to(create_player))
+        .route("recover", web::post().to(recover_player))
+        .route("{id}", web::put().to(update_player))
+        .route("{id}/profile", web::get().to(get_profile))
+        .route("{id}/recovery_code", web::post().to(new_recovery_code))
+        .route("{id}/merge", web::post().to(merge_players))
 }
 
-impl Player {
-    pub async fn save(&self, connection: &PgPool) -> bool {
-        sqlx::query!(
+/// Database operations on a [`Player`]. The type is shared with clients through
+/// `jornet_protocol`, so they are implemented through this trait.
+pub(crate) trait PlayerRecord: Sized {
+    /// Save a new player, with the hash of their recovery code.
+    async fn save(&self, recovery_code: &str, connection: &PgPool) -> bool;
+    async fn get(id: Uuid, connection: &PgPool) -> Result<Option<Self>, ApiError>;
+}
+
+impl PlayerRecord for Player {
+    async fn save(&self, recovery_code: &str, connection: &PgPool) -> bool {
+        let mut transaction = match connection.begin().await {
+            Ok(transaction) => transaction,
+            Err(_) => return false,
+        };
+        if lock_name(&self.name, &mut transaction).await.is_err() {
+            return false;
+        }
+        let inserted = sqlx::query!(
             r#"
-            INSERT INTO players (id, name, key) VALUES ($1, $2, $3)
+            INSERT INTO players (id, name, key, recovery_code) VALUES ($1, $2, $3, $4)
             "#,
             self.id,
             self.name,
             self.key,
+            recovery_code::hash(recovery_code),
         )
-        .execute(connection)
+        .execute(&mut transaction)
         .await
-        .is_ok()
+        .is_ok();
+        inserted && transaction.commit().await.is_ok()
     }
 
-    pub async fn get(id: Uuid, connection: &PgPool) -> Option<Player> {
-        sqlx::query!(
+    async fn get(id: Uuid, connection: &PgPool) -> Result<Option<Player>, ApiError> {
+        Ok(sqlx::query!(
             r#"
             SELECT id, name, key FROM players WHERE id = $1
             "#,
             id,
         )
-        .fetch_one(connection)
-        .await
+        .fetch_optional(connection)
+        .await?
         .map(|r| Player {
             id: r.id,
-            name: r.name.clone(),
+            name: r.name,
             key: r.key,
-        })
-        .ok()
+        }))
     }
 }
@@ -1,97 +1,268 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-
 use actix_cors::Cors;
-use actix_web::{dev::HttpServiceFactory, web, HttpResponse, Responder};
-use hmac::{Hmac, Mac};
+use actix_web::{dev::HttpServiceFactory, web, HttpResponse};
+use jornet_protocol::SignatureVersion;
 use serde::{Deserialize, Serialize};
-use sha2::Sha256;
 use sqlx::PgPool;
-use time::{format_description::well_known::Rfc3339, UtcOffset};
+use time::{
+    format_description::well_known::Rfc3339, Date, Duration, OffsetDateTime, PrimitiveDateTime,
+    Time, UtcOffset,
+};
 use uuid::Uuid;
 
-use super::{leaderboard::Leaderboard, player::Player};
-
-#[derive(Serialize)]
-struct Score {
-    score: f32,
-    meta: Option<String>,
-    timestamp: String,
-    player: String,
-}
-
-#[derive(Deserialize, Serialize)]
-pub struct ScoreInput {
-    pub score: f32,
-    pub player: Uuid,
-    pub meta: Option<String>,
-    pub timestamp: u64,
-    pub k: String,
-}
-
-impl ScoreInput {
-    pub fn verify_mac(&self, key: Uuid, leaderboard_key: Uuid) -> bool {
-        let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).unwrap();
-        mac.update(&self.timestamp.to_le_bytes());
-        mac.update(leaderboard_key.as_bytes());
-        mac.update(self.player.as_bytes());
-        mac.update(&self.score.to_le_bytes());
-        if let Some(meta) = self.meta.as_ref() {
-            mac.update(meta.as_bytes());
+use crate::{configuration::Settings, error::ApiError};
+
+use super::{
+    leaderboard::{Leaderboard, ScoreMode, SortOrder},
+    moderation::{BanKind, PlayerBan},
+    player::{self, Player, PlayerAuth, PlayerRecord},
+    season,
+};
+
+pub use jornet_protocol::{PlayerRanking, Score, ScoreInput, ScoreSent};
+
+/// Maximum number of scores returned in one page.
+pub(crate) const MAX_LIMIT: i64 = 1000;
+/// Number of scores returned when no limit is requested.
+pub(crate) const DEFAULT_LIMIT: i64 = 100;
+/// Maximum number of scores returned above and below a player.
+const MAX_AROUND: i64 = 50;
+/// Number of scores returned above and below a player when no count is requested.
+const DEFAULT_AROUND: i64 = 5;
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct ScoreQuery {
+    pub order: Option<SortOrder>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// The player viewing the scores, signed with their key as a [`PlayerAuth`]. They will see their
+/// own scores even if they are shadow banned.
+#[derive(Deserialize, Serialize, Default)]
+pub struct Viewer {
+    pub player: Option<Uuid>,
+    pub timestamp: Option<u64>,
+    pub k: Option<String>,
+}
+
+impl Viewer {
+    /// Check the signature of the viewer. Returns `None` if no player is viewing the scores.
+    async fn authenticate(
+        &self,
+        connection: &PgPool,
+        window: u64,
+    ) -> Result<Option<Uuid>, ApiError> {
+        let id = match self.player {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+        let auth = match (self.timestamp, self.k.as_ref()) {
+            (Some(timestamp), Some(k)) => PlayerAuth {
+                timestamp,
+                k: k.clone(),
+            },
+            _ => return Err(ApiError::InvalidSignature),
+        };
+        let player = Player::get(id, connection)
+            .await?
+            .ok_or(ApiError::UnknownPlayer)?;
+        if !auth.verify(player.id, player.key) {
+            return Err(ApiError::InvalidSignature);
         }
-        mac.verify_slice(hex::decode(&self.k).unwrap().as_slice())
-            .is_ok()
-    }
-
-    pub fn new(score: f32, player: Player, meta: Option<String>, leaderboard_key: Uuid) -> Self {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs();
-
-        let mut mac = Hmac::<Sha256>::new_from_slice(player.key.as_bytes()).unwrap();
-        mac.update(&timestamp.to_le_bytes());
-        mac.update(leaderboard_key.as_bytes());
-        mac.update(player.id.as_bytes());
-        mac.update(&score.to_le_bytes());
-        if let Some(meta) = meta.as_ref() {
-            mac.update(meta.as_bytes());
+        if !player::is_fresh(auth.timestamp, window) {
+            return Err(ApiError::TimestampOutOfWindow);
         }
+        Ok(Some(player.id))
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct AroundQuery {
+    pub count: Option<i64>,
+}
+
+/// Named time periods, starting at the beginning of the current day, week or month.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Today,
+    Week,
+    Month,
+    All,
+}
+
+/// Restrict scores to those submitted within a time window.
+#[derive(Deserialize, Serialize, Default)]
+pub struct TimeWindow {
+    pub period: Option<Period>,
+    /// Offset from UTC in minutes of the timezone used to find the start of the period.
+    pub utc_offset: Option<i32>,
+    /// Unix timestamp in seconds, inclusive.
+    pub since: Option<u64>,
+    /// Unix timestamp in seconds, exclusive.
+    pub until: Option<u64>,
+}
+
+impl TimeWindow {
+    /// Get the bounds of the window as UTC timestamps, or `None` if the window is invalid.
+    fn bounds(
+        &self,
+        now: OffsetDateTime,
+    ) -> Option<(Option<PrimitiveDateTime>, Option<PrimitiveDateTime>)> {
+        let offset =
+            UtcOffset::from_whole_seconds(self.utc_offset.unwrap_or(0).checked_mul(60)?).ok()?;
+        let now = now.to_offset(offset);
+        let start = match self.period.unwrap_or(Period::All) {
+            Period::All => None,
+            Period::Today => Some(now.replace_time(Time::MIDNIGHT)),
+            Period::Week => Some(
+                (now - Duration::days(now.weekday().number_days_from_monday().into()))
+                    .replace_time(Time::MIDNIGHT),
+            ),
+            Period::Month => Some(
+                now.replace_date(Date::from_calendar_date(now.year(), now.month(), 1).ok()?)
+                    .replace_time(Time::MIDNIGHT),
+            ),
+        };
+        let since = match self.since {
+            Some(since) => Some(OffsetDateTime::from_unix_timestamp(since.try_into().ok()?).ok()?),
+            None => None,
+        };
+        let until = match self.until {
+            Some(until) => Some(OffsetDateTime::from_unix_timestamp(until.try_into().ok()?).ok()?),
+            None => None,
+        };
 
-        let hmac = hex::encode(&mac.finalize().into_bytes()[..]);
-        Self {
-            score,
-            player: player.id,
-            meta,
-            timestamp,
-            k: hmac,
+        let to_utc = |datetime: OffsetDateTime| {
+            let datetime = datetime.to_offset(UtcOffset::UTC);
+            PrimitiveDateTime::new(datetime.date(), datetime.time())
+        };
+        Some((start.max(since).map(to_utc), until.map(to_utc)))
+    }
+}
+
+/// Check that the timestamp of a score is within `window` seconds of `now`. A score that
+/// waited to be sent must have been sent within `window` seconds of `now` instead, at most
+/// `max_delay` seconds after it was made.
+fn is_fresh(score: &ScoreInput, now: OffsetDateTime, window: u64, max_delay: u64) -> bool {
+    let now = now.unix_timestamp().max(0) as u64;
+    match &score.sent {
+        Some(sent) => {
+            sent.timestamp.abs_diff(now) <= window
+                && score.timestamp <= sent.timestamp.saturating_add(window)
+                && sent.timestamp.saturating_sub(score.timestamp) <= max_delay
         }
+        None => score.timestamp.abs_diff(now) <= window,
     }
 }
 
+/// Check that the timestamp of a score is a date that can be stored and displayed.
+fn has_valid_timestamp(score: &ScoreInput) -> bool {
+    i64::try_from(score.timestamp)
+        .ok()
+        .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+        .is_some_and(|timestamp| timestamp.year() <= 9999)
+}
+
 async fn save_score(
     connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
     score: web::Json<ScoreInput>,
     leaderboard: web::Path<Uuid>,
-) -> impl Responder {
-    if let Some(player) = Player::get(score.player, &connection).await {
-        if let Some(leaderboard_key) = Leaderboard::get_key(&connection, *leaderboard).await {
-            if score.verify_mac(player.key, leaderboard_key)
-                && Score::save(&score, &connection, &leaderboard).await
-            {
-                HttpResponse::Ok().json(())
-            } else {
-                HttpResponse::InternalServerError().finish()
-            }
-        } else {
-            HttpResponse::InternalServerError().finish()
-        }
+) -> Result<HttpResponse, ApiError> {
+    if !has_valid_timestamp(&score) {
+        return Err(ApiError::InvalidTimestamp);
+    }
+    if score.meta.as_ref().is_some_and(|meta| meta.contains('\0')) {
+        return Err(ApiError::InvalidRequest(
+            "meta can't contain null characters".to_string(),
+        ));
+    }
+    let version = match SignatureVersion::from_u8(score.version) {
+        Some(SignatureVersion::V1) if !config.accept_v1_signatures => None,
+        version => version,
+    }
+    .ok_or(ApiError::UnsupportedSignatureVersion)?;
+    let player = Player::get(score.player, &connection)
+        .await?
+        .ok_or(ApiError::UnknownPlayer)?;
+    let leaderboard = Leaderboard::get(&connection, *leaderboard)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    if !score.verify(version, player.key, leaderboard.key) || !score.verify_sent(player.key) {
+        return Err(ApiError::InvalidSignature);
+    }
+    // a score sent again after its response was lost is a duplicate, even if it's now too old
+    if Score::exists(&score, &connection, &leaderboard.id).await? {
+        return Err(ApiError::DuplicateScore);
+    }
+    if !is_fresh(
+        &score,
+        OffsetDateTime::now_utc(),
+        config.score_time_window,
+        config.score_max_delay,
+    ) {
+        return Err(ApiError::TimestampOutOfWindow);
+    }
+    if PlayerBan::get_kind(&connection, leaderboard.id, player.id).await? == Some(BanKind::Ban) {
+        return Err(ApiError::PlayerBanned);
+    }
+    leaderboard
+        .validation
+        .check(score.score)
+        .map_err(ApiError::ScoreRejected)?;
+    Score::save(&score, &connection, &leaderboard.id).await?;
+    Ok(HttpResponse::Ok().json(()))
+}
+
+async fn get_scores(
+    connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
+    leaderboard: web::Path<Uuid>,
+    query: web::Query<ScoreQuery>,
+    window: web::Query<TimeWindow>,
+    viewer: web::Query<Viewer>,
+) -> Result<HttpResponse, ApiError> {
+    let viewer = viewer
+        .authenticate(&connection, config.score_time_window)
+        .await?;
+    if let Some(leaderboard) = Leaderboard::get(&connection, *leaderboard).await? {
+        let scores = Score::get_all(
+            &connection,
+            &leaderboard,
+            &query,
+            &window,
+            leaderboard.score_mode,
+            viewer,
+        )
+        .await?;
+        Ok(HttpResponse::Ok().json(scores))
     } else {
-        HttpResponse::InternalServerError().finish()
+        Ok(HttpResponse::Ok().json(Vec::<Score>::new()))
     }
 }
 
-async fn get_scores(connection: web::Data<PgPool>, leaderboard: web::Path<Uuid>) -> impl Responder {
-    HttpResponse::Ok().json(Score::get_all(&connection, &leaderboard).await)
+async fn get_scores_around_player(
+    connection: web::Data<PgPool>,
+    config: web::Data<Settings>,
+    path: web::Path<(Uuid, Uuid)>,
+    query: web::Query<AroundQuery>,
+    window: web::Query<TimeWindow>,
+    viewer: web::Query<Viewer>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, player) = path.into_inner();
+    if window.bounds(OffsetDateTime::now_utc()).is_none() {
+        return Err(ApiError::InvalidTimeWindow);
+    }
+    let viewer = viewer
+        .authenticate(&connection, config.score_time_window)
+        .await?;
+    let leaderboard = Leaderboard::get(&connection, leaderboard)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    let ranking =
+        Score::get_around(&connection, &leaderboard, player, &query, &window, viewer).await?;
+    Ok(HttpResponse::Ok().json(ranking))
 }
 
 pub(crate) fn score() -> impl HttpServiceFactory {
@@ -105,67 +276,247 @@ pub(crate) fn score() -> impl HttpServiceFactory {
         .wrap(cors)
         .route("{leaderboard_id}", web::post().to(save_score))
         .route("{leaderboard_id}", web::get().to(get_scores))
+        .route(
+            "{leaderboard_id}/around/{player_id}",
+            web::get().to(get_scores_around_player),
+        )
+        .route(
+            "{leaderboard_id}/seasons",
+            web::get().to(season::get_seasons),
+        )
+        .route(
+            "{leaderboard_id}/seasons/{season}",
+            web::get().to(season::get_season_scores),
+        )
+}
+
+/// Format a timestamp from the database as RFC 3339.
+pub(crate) fn format_timestamp(timestamp: PrimitiveDateTime) -> Result<String, ApiError> {
+    timestamp
+        .assume_offset(UtcOffset::UTC)
+        .format(&Rfc3339)
+        .map_err(|_| ApiError::Internal)
+}
+
+/// Database operations on scores. [`Score`] is shared with clients through `jornet_protocol`, so
+/// they are implemented through this trait.
+pub(crate) trait ScoreRecord {
+    /// Get a page of ranked scores from a leaderboard.
+    ///
+    /// With [`ScoreMode::Best`] or [`ScoreMode::Latest`], only one score per player is kept,
+    /// selected according to the leaderboard sort order, or the submission time.
+    ///
+    /// Scores of shadow banned players are hidden, except from the `viewer` themselves.
+    async fn get_all(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        query: &ScoreQuery,
+        window: &TimeWindow,
+        mode: ScoreMode,
+        viewer: Option<Uuid>,
+    ) -> Result<Vec<Score>, ApiError>;
+
+    /// Get the best rank of a player, and the scores surrounding it.
+    ///
+    /// Scores of shadow banned players are hidden, except from the `viewer` themselves.
+    async fn get_around(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        player: Uuid,
+        query: &AroundQuery,
+        window: &TimeWindow,
+        viewer: Option<Uuid>,
+    ) -> Result<PlayerRanking, ApiError>;
+
+    /// Check if a score with the same signature was already saved.
+    async fn exists(
+        score: &ScoreInput,
+        connection: &PgPool,
+        leaderboard: &Uuid,
+    ) -> Result<bool, ApiError>;
+
+    /// Save a score. Its signature is kept so that the same submission can't be saved twice.
+    async fn save(
+        score: &ScoreInput,
+        connection: &PgPool,
+        leaderboard: &Uuid,
+    ) -> Result<(), ApiError>;
 }
 
-impl Score {
-    pub async fn get_all(connection: &PgPool, leaderboard: &Uuid) -> Vec<Score> {
+impl ScoreRecord for Score {
+    async fn get_all(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        query: &ScoreQuery,
+        window: &TimeWindow,
+        mode: ScoreMode,
+        viewer: Option<Uuid>,
+    ) -> Result<Vec<Score>, ApiError> {
+        let (since, until) = window
+            .bounds(OffsetDateTime::now_utc())
+            .ok_or(ApiError::InvalidTimeWindow)?;
+        // ranks follow the sort order of the leaderboard, the query only changes how they are listed
+        let descending = query.order.unwrap_or(leaderboard.sort_order) == SortOrder::Descending;
+        let best_is_highest = leaderboard.sort_order == SortOrder::Descending;
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let offset = query.offset.unwrap_or(0).max(0);
+
         sqlx::query!(
-            "SELECT score, meta, timestamp, players.name FROM scores, players WHERE leaderboard = $1 and scores.player = players.id",
-            leaderboard
+            r#"
+            SELECT score as "score!", meta, timestamp as "timestamp!", name as "name!", rank as "rank!" FROM (
+                SELECT score, meta, timestamp, name,
+                    RANK() OVER (ORDER BY CASE WHEN $6 THEN score ELSE -score END DESC) AS rank
+                FROM kept_scores($1, $9, $5, $6, $7, $8, $10) AS kept
+            ) AS ranked
+            ORDER BY CASE WHEN $2 = $6 THEN rank ELSE -rank END, timestamp
+            LIMIT $3 OFFSET $4
+            "#,
+            leaderboard.id,
+            descending,
+            limit,
+            offset,
+            mode.as_str(),
+            best_is_highest,
+            since,
+            until,
+            leaderboard.season,
+            viewer,
         )
         .fetch_all(connection)
-        .await
-        .unwrap()
-        .iter()
-        .map(|r| Score {
-            score: r.score,
-            meta: r.meta.clone(),
-            player: r.name.clone(),
-            timestamp: r
-                .timestamp
-                .assume_offset(UtcOffset::UTC)
-                .format(&Rfc3339)
-                .unwrap(),
+        .await?
+        .into_iter()
+        .map(|r| {
+            Ok(Score {
+                score: r.score,
+                meta: r.meta,
+                player: r.name,
+                rank: r.rank,
+                timestamp: format_timestamp(r.timestamp)?,
+            })
         })
         .collect()
     }
 
-    pub async fn save(score: &ScoreInput, connection: &PgPool, leaderboard: &Uuid) -> bool {
+    async fn get_around(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+        player: Uuid,
+        query: &AroundQuery,
+        window: &TimeWindow,
+        viewer: Option<Uuid>,
+    ) -> Result<PlayerRanking, ApiError> {
+        let (since, until) = window
+            .bounds(OffsetDateTime::now_utc())
+            .ok_or(ApiError::InvalidTimeWindow)?;
+        let descending = leaderboard.sort_order == SortOrder::Descending;
+        let count = query.count.unwrap_or(DEFAULT_AROUND).clamp(0, MAX_AROUND);
+
+        let rows = sqlx::query!(
+            r#"
+            WITH ranked AS (
+                SELECT score, meta, timestamp, name, player,
+                    RANK() OVER (ORDER BY CASE WHEN $2 THEN score ELSE -score END DESC) AS rank,
+                    ROW_NUMBER() OVER (ORDER BY CASE WHEN $2 THEN score ELSE -score END DESC, timestamp) AS position
+                FROM kept_scores($1, $8, $3, $2, $6, $7, $9) AS kept
+            ), target AS (
+                SELECT position FROM ranked WHERE player = $4 ORDER BY position LIMIT 1
+            )
+            SELECT score as "score!", meta, timestamp as "timestamp!", name as "name!", rank as "rank!",
+                ranked.position = target.position as "is_target!"
+            FROM ranked, target
+            WHERE ranked.position BETWEEN target.position - $5 AND target.position + $5
+            ORDER BY ranked.position
+            "#,
+            leaderboard.id,
+            descending,
+            leaderboard.score_mode.as_str(),
+            player,
+            count,
+            since,
+            until,
+            leaderboard.season,
+            viewer,
+        )
+        .fetch_all(connection)
+        .await?;
+
+        let rank = rows
+            .iter()
+            .find(|r| r.is_target)
+            .ok_or(ApiError::PlayerNotRanked)?
+            .rank;
+        Ok(PlayerRanking {
+            rank,
+            scores: rows
+                .into_iter()
+                .map(|r| {
+                    Ok(Score {
+                        score: r.score,
+                        meta: r.meta,
+                        player: r.name,
+                        rank: r.rank,
+                        timestamp: format_timestamp(r.timestamp)?,
+                    })
+                })
+                .collect::<Result<_, ApiError>>()?,
+        })
+    }
+
+    async fn exists(
+        score: &ScoreInput,
+        connection: &PgPool,
+        leaderboard: &Uuid,
+    ) -> Result<bool, ApiError> {
+        let signature = hex::decode(&score.k).map_err(|_| ApiError::InvalidSignature)?;
+        Ok(sqlx::query!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM scores WHERE leaderboard = $1 AND signature = $2)
+                AS "exists!"
+            "#,
+            leaderboard,
+            signature,
+        )
+        .fetch_one(connection)
+        .await?
+        .exists)
+    }
+
+    async fn save(
+        score: &ScoreInput,
+        connection: &PgPool,
+        leaderboard: &Uuid,
+    ) -> Result<(), ApiError> {
         if sqlx::query!("SELECT id FROM leaderboards WHERE id = $1", leaderboard)
-            .fetch_one(connection)
-            .await
-            .is_err()
+            .fetch_optional(connection)
+            .await?
+            .is_none()
         {
-            return false;
+            return Err(ApiError::UnknownLeaderboard);
         }
 
-        if sqlx::query!(
-            "SELECT id FROM scores WHERE leaderboard = $1 AND player = $2 AND score = $3 AND timestamp = TO_TIMESTAMP($4)",
+        let signature = hex::decode(&score.k).map_err(|_| ApiError::InvalidSignature)?;
+        // share lock the leaderboard so that its season can't be closed while the score is saved
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO scores (id, leaderboard, score, player, meta, timestamp, season, signature)
+            SELECT $1, $2, $3, $4, $5, TO_TIMESTAMP($6), season, $7 FROM leaderboards WHERE id = $2
+            FOR SHARE
+            ON CONFLICT (leaderboard, signature) DO NOTHING
+            "#,
+            Uuid::new_v4(),
             leaderboard,
-            score.player,
             score.score,
-            score.timestamp as f64
+            score.player,
+            score.meta,
+            score.timestamp as f64,
+            signature,
         )
-            .fetch_one(connection)
-            .await
-            .is_ok()
-        {
-            return false;
+        .execute(connection)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            // a submission with the same signature was already saved
+            return Err(ApiError::DuplicateScore);
         }
-
-        sqlx::query!(
-                r#"
-            INSERT INTO scores (id, leaderboard, score, player, meta, timestamp) VALUES ($1, $2, $3, $4, $5, TO_TIMESTAMP($6))
-            "#,
-                Uuid::new_v4(),
-                leaderboard,
-                score.score,
-                score.player,
-                score.meta,
-                score.timestamp as f64
-            )
-            .execute(connection)
-            .await
-        .is_ok()
+        Ok(())
     }
 }
@@ -0,0 +1,162 @@
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+use super::{
+    admin::AdminAccount,
+    leaderboard::{Leaderboard, SortOrder},
+    score::{format_timestamp, Score, ScoreQuery, DEFAULT_LIMIT, MAX_LIMIT},
+};
+
+/// A closed season of a leaderboard. Its final standings are archived and can't be changed.
+#[derive(Deserialize, Serialize)]
+pub struct Season {
+    pub season: i32,
+    pub ended_at: String,
+}
+
+/// The season that started after closing the previous one.
+#[derive(Deserialize, Serialize)]
+pub struct NewSeason {
+    pub season: i32,
+}
+
+pub(crate) async fn close_season(
+    account: web::ReqData<AdminAccount>,
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let leaderboard = Leaderboard::get_owned(&connection, *leaderboard, account.id)
+        .await?
+        .ok_or(ApiError::UnknownLeaderboard)?;
+    let season = Season::close(&connection, &leaderboard).await?;
+    Ok(HttpResponse::Ok().json(season))
+}
+
+pub(crate) async fn get_seasons(
+    connection: web::Data<PgPool>,
+    leaderboard: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().json(Season::get_all(&connection, *leaderboard).await?))
+}
+
+pub(crate) async fn get_season_scores(
+    connection: web::Data<PgPool>,
+    path: web::Path<(Uuid, i32)>,
+    query: web::Query<ScoreQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (leaderboard, season) = path.into_inner();
+    Ok(
+        HttpResponse::Ok()
+            .json(Season::get_scores(&connection, leaderboard, season, &query).await?),
+    )
+}
+
+impl Season {
+    /// Close the current season of a leaderboard, archive its standings, and start a new one.
+    pub async fn close(
+        connection: &PgPool,
+        leaderboard: &Leaderboard,
+    ) -> Result<NewSeason, ApiError> {
+        let mut transaction = connection.begin().await?;
+
+        // lock the leaderboard so that no other season can be closed at the same time
+        let season = sqlx::query!(
+            "SELECT season FROM leaderboards WHERE id = $1 FOR UPDATE",
+            leaderboard.id
+        )
+        .fetch_one(&mut transaction)
+        .await?
+        .season;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO season_standings (leaderboard, season, rank, score, player, name, meta, timestamp)
+            SELECT $1, $2, RANK() OVER (ORDER BY CASE WHEN $3 THEN score ELSE -score END DESC), score, player, name, meta, timestamp
+            FROM kept_scores($1, $2, $4, $3, NULL, NULL, NULL) AS kept
+            "#,
+            leaderboard.id,
+            season,
+            leaderboard.sort_order == SortOrder::Descending,
+            leaderboard.score_mode.as_str(),
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO seasons (leaderboard, season, ended_at) VALUES ($1, $2, NOW())",
+            leaderboard.id,
+            season
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE leaderboards SET season = season + 1 WHERE id = $1",
+            leaderboard.id
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(NewSeason { season: season + 1 })
+    }
+
+    pub async fn get_all(connection: &PgPool, leaderboard: Uuid) -> Result<Vec<Season>, ApiError> {
+        sqlx::query!(
+            "SELECT season, ended_at FROM seasons WHERE leaderboard = $1 ORDER BY season",
+            leaderboard
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(|r| {
+            Ok(Season {
+                season: r.season,
+                ended_at: format_timestamp(r.ended_at)?,
+            })
+        })
+        .collect()
+    }
+
+    /// Get a page of the final standings of a closed season.
+    pub async fn get_scores(
+        connection: &PgPool,
+        leaderboard: Uuid,
+        season: i32,
+        query: &ScoreQuery,
+    ) -> Result<Vec<Score>, ApiError> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let offset = query.offset.unwrap_or(0).max(0);
+
+        sqlx::query!(
+            r#"
+            SELECT score, meta, timestamp, name, rank FROM season_standings
+            WHERE leaderboard = $1 AND season = $2
+            ORDER BY rank, timestamp
+            LIMIT $3 OFFSET $4
+            "#,
+            leaderboard,
+            season,
+            limit,
+            offset
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(|r| {
+            Ok(Score {
+                score: r.score,
+                meta: r.meta,
+                player: r.name,
+                rank: r.rank,
+                timestamp: format_timestamp(r.timestamp)?,
+            })
+        })
+        .collect()
+    }
+}
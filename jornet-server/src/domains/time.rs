@@ -0,0 +1,26 @@
+use actix_cors::Cors;
+use actix_web::{dev::HttpServiceFactory, web, HttpResponse, Responder};
+use time::OffsetDateTime;
+
+use crate::configuration::Settings;
+
+pub use jornet_protocol::ServerTime;
+
+async fn get_time(config: web::Data<Settings>) -> impl Responder {
+    HttpResponse::Ok().json(ServerTime {
+        timestamp: OffsetDateTime::now_utc().unix_timestamp().max(0) as u64,
+        score_time_window: config.score_time_window,
+    })
+}
+
+pub(crate) fn time() -> impl HttpServiceFactory {
+    let cors = Cors::default()
+        .allow_any_header()
+        .allow_any_origin()
+        .allow_any_method()
+        .send_wildcard()
+        .max_age(3600);
+    web::scope("api/v1/time")
+        .wrap(cors)
+        .route("", web::get().to(get_time))
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use crate::{domains::leaderboard::ScoreRejection, player_name::NameRejection};
+
+pub use jornet_protocol::ErrorBody;
+
+/// Errors returned by the API. They are sent as JSON with a stable `code` that clients can
+/// match on, see [`ErrorBody`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// The request body, path or query could not be parsed.
+    InvalidRequest(String),
+    /// The name chosen by a player breaks the name rules of the server.
+    InvalidName(NameRejection),
+    InvalidTimeWindow,
+    /// The timestamp of the score is not a valid date.
+    InvalidTimestamp,
+    /// The timestamp of the score is too far from the server time, see `api/v1/time`.
+    TimestampOutOfWindow,
+    /// The score was signed with an unknown or retired version of the signing scheme.
+    UnsupportedSignatureVersion,
+    /// Missing or invalid admin token.
+    Unauthorized,
+    /// The score or player update was not signed with the key of the player, and of the
+    /// leaderboard for a score.
+    InvalidSignature,
+    /// The recovery code is not the current code of any player.
+    InvalidRecoveryCode,
+    DuplicateScore,
+    /// A more recent update of the player was already applied.
+    StalePlayerUpdate,
+    PlayerBanned,
+    ScoreRejected(ScoreRejection),
+    /// The account is linked to GitHub and must log in through it.
+    GithubAccount,
+    UnknownPlayer,
+    UnknownLeaderboard,
+    UnknownScore,
+    /// The player has no score in the leaderboard.
+    PlayerNotRanked,
+    PlayerNotBanned,
+    /// GitHub could not be reached during login.
+    Github,
+    Internal,
+}
+
+impl ApiError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::InvalidRequest(_) => "invalid_request",
+            ApiError::InvalidName(NameRejection::Empty) => "name_empty",
+            ApiError::InvalidName(NameRejection::TooLong) => "name_too_long",
+            ApiError::InvalidName(NameRejection::InvalidCharacters) => "name_invalid_characters",
+            ApiError::InvalidName(NameRejection::Blocked) => "name_blocked",
+            ApiError::InvalidTimeWindow => "invalid_time_window",
+            ApiError::InvalidTimestamp => "invalid_timestamp",
+            ApiError::TimestampOutOfWindow => "timestamp_out_of_window",
+            ApiError::UnsupportedSignatureVersion => "unsupported_signature_version",
+            ApiError::Unauthorized => "unauthorized",
+            ApiError::InvalidSignature => "invalid_signature",
+            ApiError::InvalidRecoveryCode => "invalid_recovery_code",
+            ApiError::DuplicateScore => "duplicate_score",
+            ApiError::StalePlayerUpdate => "stale_player_update",
+            ApiError::PlayerBanned => "player_banned",
+            ApiError::ScoreRejected(ScoreRejection::NotFinite) => "score_not_finite",
+            ApiError::ScoreRejected(ScoreRejection::BelowMin) => "score_below_min",
+            ApiError::ScoreRejected(ScoreRejection::AboveMax) => "score_above_max",
+            ApiError::ScoreRejected(ScoreRejection::NotInteger) => "score_not_integer",
+            ApiError::GithubAccount => "github_account",
+            ApiError::UnknownPlayer => "unknown_player",
+            ApiError::UnknownLeaderboard => "unknown_leaderboard",
+            ApiError::UnknownScore => "unknown_score",
+            ApiError::PlayerNotRanked => "player_not_ranked",
+            ApiError::PlayerNotBanned => "player_not_banned",
+            ApiError::Github => "github_error",
+            ApiError::Internal => "internal_error",
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::InvalidRequest(reason) => write!(f, "invalid request: {}", reason),
+            ApiError::InvalidName(NameRejection::Empty) => write!(f, "name can't be empty"),
+            ApiError::InvalidName(NameRejection::TooLong) => write!(f, "name is too long"),
+            ApiError::InvalidName(NameRejection::InvalidCharacters) => {
+                write!(f, "name contains characters that are not allowed")
+            }
+            ApiError::InvalidName(NameRejection::Blocked) => {
+                write!(f, "name contains a word that is not allowed")
+            }
+            ApiError::InvalidTimeWindow => write!(f, "invalid time window"),
+            ApiError::InvalidTimestamp => write!(f, "invalid score timestamp"),
+            ApiError::TimestampOutOfWindow => {
+                write!(f, "score timestamp is too far from the server time")
+            }
+            ApiError::UnsupportedSignatureVersion => write!(f, "unsupported signature version"),
+            ApiError::Unauthorized => write!(f, "missing or invalid token"),
+            ApiError::InvalidSignature => write!(f, "invalid signature"),
+            ApiError::InvalidRecoveryCode => write!(f, "invalid recovery code"),
+            ApiError::DuplicateScore => write!(f, "score already saved"),
+            ApiError::StalePlayerUpdate => write!(f, "a more recent update was already applied"),
+            ApiError::PlayerBanned => write!(f, "player is banned from this leaderboard"),
+            ApiError::ScoreRejected(ScoreRejection::NotFinite) => write!(f, "score is not finite"),
+            ApiError::ScoreRejected(ScoreRejection::BelowMin) => {
+                write!(f, "score is below the minimum of the leaderboard")
+            }
+            ApiError::ScoreRejected(ScoreRejection::AboveMax) => {
+                write!(f, "score is above the maximum of the leaderboard")
+            }
+            ApiError::ScoreRejected(ScoreRejection::NotInteger) => {
+                write!(f, "score must be a whole number")
+            }
+            ApiError::GithubAccount => write!(f, "account must log in with GitHub"),
+            ApiError::UnknownPlayer => write!(f, "unknown player"),
+            ApiError::UnknownLeaderboard => write!(f, "unknown leaderboard"),
+            ApiError::UnknownScore => write!(f, "unknown score"),
+            ApiError::PlayerNotRanked => write!(f, "player has no score in this leaderboard"),
+            ApiError::PlayerNotBanned => write!(f, "player is not banned from this leaderboard"),
+            ApiError::Github => write!(f, "error while logging in with GitHub"),
+            ApiError::Internal => write!(f, "internal error"),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::InvalidRequest(_)
+            | ApiError::InvalidName(_)
+            | ApiError::InvalidTimeWindow
+            | ApiError::InvalidTimestamp
+            | ApiError::TimestampOutOfWindow
+            | ApiError::UnsupportedSignatureVersion
+            | ApiError::ScoreRejected(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized | ApiError::InvalidSignature | ApiError::InvalidRecoveryCode => {
+                StatusCode::UNAUTHORIZED
+            }
+            ApiError::PlayerBanned | ApiError::GithubAccount => StatusCode::FORBIDDEN,
+            ApiError::UnknownPlayer
+            | ApiError::UnknownLeaderboard
+            | ApiError::UnknownScore
+            | ApiError::PlayerNotRanked
+            | ApiError::PlayerNotBanned => StatusCode::NOT_FOUND,
+            ApiError::DuplicateScore | ApiError::StalePlayerUpdate => StatusCode::CONFLICT,
+            ApiError::Github => StatusCode::BAD_GATEWAY,
+            ApiError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ErrorBody {
+            code: self.code().to_string(),
+            message: self.to_string(),
+        })
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        log::error!("database error: {}", error);
+        ApiError::Internal
+    }
+}
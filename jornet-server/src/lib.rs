@@ -1,4 +1,4 @@
-use std::{net::TcpListener, path::PathBuf, str::FromStr};
+use std::{net::TcpListener, path::PathBuf, sync::Arc};
 
 use actix_files::NamedFile;
 use actix_web::{
@@ -7,17 +7,23 @@ use actix_web::{
     web::{self, Data},
     App, HttpRequest, HttpServer, Result,
 };
-use configuration::get_configuration;
+use configuration::{get_configuration, Settings};
+use error::ApiError;
+use player_name::NameValidator;
+use random_name::{NameGenerator, WordLists};
 use sqlx::PgPool;
 
 pub mod auth_admin;
 pub mod configuration;
 pub mod domains;
+pub mod error;
+pub mod player_name;
 pub mod random_name;
+pub mod recovery_code;
 
 async fn spa(req: HttpRequest) -> Result<NamedFile> {
-    let path: PathBuf = req.match_info().query("filename").parse().unwrap();
-    let mut static_path = PathBuf::from_str("static").unwrap();
+    let path = PathBuf::from(req.match_info().query("filename"));
+    let mut static_path = PathBuf::from("static");
     if path.extension().is_some() {
         static_path.push(path);
     } else {
@@ -27,15 +33,46 @@ async fn spa(req: HttpRequest) -> Result<NamedFile> {
 }
 
 pub fn run(listener: TcpListener, connection_pool: PgPool) -> Result<Server, std::io::Error> {
-    let config = Data::new(get_configuration());
+    let config = get_configuration();
+    let names = NameValidator::from_settings(&config.player_names);
+    let random_names = WordLists::from_settings(&config.random_names);
+    run_with(listener, connection_pool, config, names, random_names)
+}
+
+/// Run the server, checking player names with `names` and generating random names with
+/// `random_names` instead of building them from the configuration.
+pub fn run_with(
+    listener: TcpListener,
+    connection_pool: PgPool,
+    config: Settings,
+    names: NameValidator,
+    random_names: impl NameGenerator + 'static,
+) -> Result<Server, std::io::Error> {
+    let config = Data::new(config);
     let root = Data::new(config.get_keypair());
     let connection = Data::new(connection_pool);
+    let names = Data::new(names);
+    let random_names: Data<dyn NameGenerator> = Data::from(Arc::new(random_names) as Arc<_>);
 
     let server = HttpServer::new(move || {
         App::new()
             .app_data(connection.clone())
             .app_data(root.clone())
             .app_data(config.clone())
+            .app_data(names.clone())
+            .app_data(random_names.clone())
+            .app_data(
+                web::JsonConfig::default()
+                    .error_handler(|err, _| ApiError::InvalidRequest(err.to_string()).into()),
+            )
+            .app_data(
+                web::QueryConfig::default()
+                    .error_handler(|err, _| ApiError::InvalidRequest(err.to_string()).into()),
+            )
+            .app_data(
+                web::PathConfig::default()
+                    .error_handler(|err, _| ApiError::InvalidRequest(err.to_string()).into()),
+            )
             .wrap(Logger::default())
             .route(
                 "/health_check",
@@ -47,6 +84,7 @@ pub fn run(listener: TcpListener, connection_pool: PgPool) -> Result<Server, std
             .service(domains::leaderboard::leaderboard(root.clone()))
             .service(domains::player::player())
             .service(domains::score::score())
+            .service(domains::time::time())
             .route("/{filename:.*}", web::get().to(spa))
     })
     .listen(listener)?
@@ -5,6 +5,8 @@ use sqlx::PgPool;
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt::init();
+
     let configuration = get_configuration();
 
     let address = format!(
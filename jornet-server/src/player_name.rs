@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::configuration::PlayerNameSettings;
+
+/// Rule of [`NameValidator`] that rejected a name.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NameRejection {
+    Empty,
+    TooLong,
+    InvalidCharacters,
+    Blocked,
+}
+
+/// Filter on the content of player names, as they are displayed publicly.
+pub trait NameFilter: Send + Sync {
+    /// Check a name that already follows the [`PlayerNameSettings`].
+    fn is_allowed(&self, name: &str) -> bool;
+}
+
+/// [`NameFilter`] rejecting names containing a word from a list.
+///
+/// Each word of a name is compared ignoring case, punctuation and common letter substitutions,
+/// so that "F.u_Ck" or "5h1t" are still found. Letters spelled out one by one, as in
+/// "f u c k", are compared as a single word.
+///
+/// A word of the list only matches whole words, so that "Scunthorpe" is not rejected for
+/// containing "cunt". A `*` at the start or the end of the word in the list matches any letters
+/// there: `cunt*` also matches "cunts", `*shit` also matches "bullshit", and `*fuck*` matches
+/// any word containing "fuck".
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    words: Vec<BlockedWord>,
+}
+
+#[derive(Debug, Clone)]
+struct BlockedWord {
+    word: String,
+    any_start: bool,
+    any_end: bool,
+}
+
+impl BlockedWord {
+    fn matches(&self, name_word: &str) -> bool {
+        match (self.any_start, self.any_end) {
+            (false, false) => name_word == self.word,
+            (false, true) => name_word.starts_with(&self.word),
+            (true, false) => name_word.ends_with(&self.word),
+            (true, true) => name_word.contains(&self.word),
+        }
+    }
+}
+
+impl Blocklist {
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            words: words
+                .into_iter()
+                .map(|word| {
+                    let word = word.as_ref().trim();
+                    BlockedWord {
+                        word: fold(word),
+                        any_start: word.starts_with('*'),
+                        any_end: word.ends_with('*'),
+                    }
+                })
+                .filter(|word| !word.word.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Read a list with one word per line. Lines starting with `#` are ignored.
+    pub fn parse(list: &str) -> Self {
+        Self::new(
+            list.lines()
+                .map(str::trim)
+                .filter(|line| !line.starts_with('#')),
+        )
+    }
+}
+
+impl NameFilter for Blocklist {
+    fn is_allowed(&self, name: &str) -> bool {
+        let mut name_words: Vec<String> = vec![];
+        let mut spelled_out = String::new();
+        for word in name.split_whitespace().map(fold) {
+            if word.chars().count() == 1 {
+                spelled_out.push_str(&word);
+            } else {
+                name_words.push(std::mem::take(&mut spelled_out));
+                name_words.push(word);
+            }
+        }
+        name_words.push(spelled_out);
+        !name_words
+            .iter()
+            .filter(|name_word| !name_word.is_empty())
+            .any(|name_word| self.words.iter().any(|word| word.matches(name_word)))
+    }
+}
+
+/// Lowercase a word, undo common letter substitutions and only keep letters and digits.
+fn fold(word: &str) -> String {
+    word.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// Check and normalize the names chosen by players.
+pub struct NameValidator {
+    rules: PlayerNameSettings,
+    filter: Box<dyn NameFilter>,
+}
+
+impl NameValidator {
+    pub fn new(rules: PlayerNameSettings, filter: impl NameFilter + 'static) -> Self {
+        Self {
+            rules,
+            filter: Box::new(filter),
+        }
+    }
+
+    /// Use the rules and the [`Blocklist`] from the configuration.
+    pub fn from_settings(rules: &PlayerNameSettings) -> Self {
+        Self::new(rules.clone(), Blocklist::parse(&rules.blocklist))
+    }
+
+    /// Get the name to save, NFC normalized with whitespace trimmed and collapsed, or the rule
+    /// it breaks.
+    pub fn validate(&self, name: &str) -> Result<String, NameRejection> {
+        let name = name
+            .nfc()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            return Err(NameRejection::Empty);
+        }
+        if name.chars().count() > self.rules.max_length {
+            return Err(NameRejection::TooLong);
+        }
+        if !name.chars().all(|c| self.is_allowed_character(c)) {
+            return Err(NameRejection::InvalidCharacters);
+        }
+        if !self.filter.is_allowed(&name) {
+            return Err(NameRejection::Blocked);
+        }
+        Ok(name)
+    }
+
+    fn is_allowed_character(&self, c: char) -> bool {
+        (c.is_ascii() || !self.rules.ascii_only)
+            && (c.is_alphanumeric() || c == ' ' || self.rules.allowed_symbols.contains(c))
+    }
+}
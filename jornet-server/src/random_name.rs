@@ -1,827 +1,176 @@
-use rand::seq::SliceRandom;
+use std::collections::HashMap;
 
-pub fn random_name() -> String {
-    let mut rng = rand::thread_rng();
-    format!(
-        "{} {}",
-        ADJECTIVES.choose(&mut rng).unwrap(),
-        POKEMONS.choose(&mut rng).unwrap(),
-    )
+use rand::{seq::SliceRandom, Rng};
+
+use crate::configuration::RandomNameSettings;
+
+/// Generator of names for players that didn't choose one.
+pub trait NameGenerator: Send + Sync {
+    /// Generate a name, in the `theme` and `locale` requested by the player if they are
+    /// available.
+    fn generate(&self, theme: Option<&str>, locale: Option<&str>) -> String;
 }
 
-const POKEMONS: &[&str] = &[
-    "Bulbasaur",
-    "Ivysaur",
-    "Venusaur",
-    "Charmander",
-    "Charmeleon",
-    "Charizard",
-    "Squirtle",
-    "Wartortle",
-    "Blastoise",
-    "Caterpie",
-    "Metapod",
-    "Butterfree",
-    "Weedle",
-    "Kakuna",
-    "Beedrill",
-    "Pidgey",
-    "Pidgeotto",
-    "Pidgeot",
-    "Rattata",
-    "Raticate",
-    "Spearow",
-    "Fearow",
-    "Ekans",
-    "Arbok",
-    "Pikachu",
-    "Raichu",
-    "Sandshrew",
-    "Sandslash",
-    "Nidoran-F",
-    "Nidorina",
-    "Nidoqueen",
-    "Nidoran-M",
-    "Nidorino",
-    "Nidoking",
-    "Clefairy",
-    "Clefable",
-    "Vulpix",
-    "Ninetales",
-    "Jigglypuff",
-    "Wigglytuff",
-    "Zubat",
-    "Golbat",
-    "Oddish",
-    "Gloom",
-    "Vileplume",
-    "Paras",
-    "Parasect",
-    "Venonat",
-    "Venomoth",
-    "Diglett",
-    "Dugtrio",
-    "Meowth",
-    "Persian",
-    "Psyduck",
-    "Golduck",
-    "Mankey",
-    "Primeape",
-    "Growlithe",
-    "Arcanine",
-    "Poliwag",
-    "Poliwhirl",
-    "Poliwrath",
-    "Abra",
-    "Kadabra",
-    "Alakazam",
-    "Machop",
-    "Machoke",
-    "Machamp",
-    "Bellsprout",
-    "Weepinbell",
-    "Victreebel",
-    "Tentacool",
-    "Tentacruel",
-    "Geodude",
-    "Graveler",
-    "Golem",
-    "Ponyta",
-    "Rapidash",
-    "Slowpoke",
-    "Slowbro",
-    "Magnemite",
-    "Magneton",
-    "Farfetchd",
-    "Doduo",
-    "Dodrio",
-    "Seel",
-    "Dewgong",
-    "Grimer",
-    "Muk",
-    "Shellder",
-    "Cloyster",
-    "Gastly",
-    "Haunter",
-    "Gengar",
-    "Onix",
-    "Drowzee",
-    "Hypno",
-    "Krabby",
-    "Kingler",
-    "Voltorb",
-    "Electrode",
-    "Exeggcute",
-    "Exeggutor",
-    "Cubone",
-    "Marowak",
-    "Hitmonlee",
-    "Hitmonchan",
-    "Lickitung",
-    "Koffing",
-    "Weezing",
-    "Rhyhorn",
-    "Rhydon",
-    "Chansey",
-    "Tangela",
-    "Kangaskhan",
-    "Horsea",
-    "Seadra",
-    "Goldeen",
-    "Seaking",
-    "Staryu",
-    "Starmie",
-    "Mr-Mime",
-    "Scyther",
-    "Jynx",
-    "Electabuzz",
-    "Magmar",
-    "Pinsir",
-    "Tauros",
-    "Magikarp",
-    "Gyarados",
-    "Lapras",
-    "Ditto",
-    "Eevee",
-    "Vaporeon",
-    "Jolteon",
-    "Flareon",
-    "Porygon",
-    "Omanyte",
-    "Omastar",
-    "Kabuto",
-    "Kabutops",
-    "Aerodactyl",
-    "Snorlax",
-    "Articuno",
-    "Zapdos",
-    "Moltres",
-    "Dratini",
-    "Dragonair",
-    "Dragonite",
-    "Mewtwo",
-    "Mew",
-    "Chikorita",
-    "Bayleef",
-    "Meganium",
-    "Cyndaquil",
-    "Quilava",
-    "Typhlosion",
-    "Totodile",
-    "Croconaw",
-    "Feraligatr",
-    "Sentret",
-    "Furret",
-    "Hoothoot",
-    "Noctowl",
-    "Ledyba",
-    "Ledian",
-    "Spinarak",
-    "Ariados",
-    "Crobat",
-    "Chinchou",
-    "Lanturn",
-    "Pichu",
-    "Cleffa",
-    "Igglybuff",
-    "Togepi",
-    "Togetic",
-    "Natu",
-    "Xatu",
-    "Mareep",
-    "Flaaffy",
-    "Ampharos",
-    "Bellossom",
-    "Marill",
-    "Azumarill",
-    "Sudowoodo",
-    "Politoed",
-    "Hoppip",
-    "Skiploom",
-    "Jumpluff",
-    "Aipom",
-    "Sunkern",
-    "Sunflora",
-    "Yanma",
-    "Wooper",
-    "Quagsire",
-    "Espeon",
-    "Umbreon",
-    "Murkrow",
-    "Slowking",
-    "Misdreavus",
-    "Unown",
-    "Wobbuffet",
-    "Girafarig",
-    "Pineco",
-    "Forretress",
-    "Dunsparce",
-    "Gligar",
-    "Steelix",
-    "Snubbull",
-    "Granbull",
-    "Qwilfish",
-    "Scizor",
-    "Shuckle",
-    "Heracross",
-    "Sneasel",
-    "Teddiursa",
-    "Ursaring",
-    "Slugma",
-    "Magcargo",
-    "Swinub",
-    "Piloswine",
-    "Corsola",
-    "Remoraid",
-    "Octillery",
-    "Delibird",
-    "Mantine",
-    "Skarmory",
-    "Houndour",
-    "Houndoom",
-    "Kingdra",
-    "Phanpy",
-    "Donphan",
-    "Porygon2",
-    "Stantler",
-    "Smeargle",
-    "Tyrogue",
-    "Hitmontop",
-    "Smoochum",
-    "Elekid",
-    "Magby",
-    "Miltank",
-    "Blissey",
-    "Raikou",
-    "Entei",
-    "Suicune",
-    "Larvitar",
-    "Pupitar",
-    "Tyranitar",
-    "Lugia",
-    "Ho-Oh",
-    "Celebi",
-    "Treecko",
-    "Grovyle",
-    "Sceptile",
-    "Torchic",
-    "Combusken",
-    "Blaziken",
-    "Mudkip",
-    "Marshtomp",
-    "Swampert",
-    "Poochyena",
-    "Mightyena",
-    "Zigzagoon",
-    "Linoone",
-    "Wurmple",
-    "Silcoon",
-    "Beautifly",
-    "Cascoon",
-    "Dustox",
-    "Lotad",
-    "Lombre",
-    "Ludicolo",
-    "Seedot",
-    "Nuzleaf",
-    "Shiftry",
-    "Taillow",
-    "Swellow",
-    "Wingull",
-    "Pelipper",
-    "Ralts",
-    "Kirlia",
-    "Gardevoir",
-    "Surskit",
-    "Masquerain",
-    "Shroomish",
-    "Breloom",
-    "Slakoth",
-    "Vigoroth",
-    "Slaking",
-    "Nincada",
-    "Ninjask",
-    "Shedinja",
-    "Whismur",
-    "Loudred",
-    "Exploud",
-    "Makuhita",
-    "Hariyama",
-    "Azurill",
-    "Nosepass",
-    "Skitty",
-    "Delcatty",
-    "Sableye",
-    "Mawile",
-    "Aron",
-    "Lairon",
-    "Aggron",
-    "Meditite",
-    "Medicham",
-    "Electrike",
-    "Manectric",
-    "Plusle",
-    "Minun",
-    "Volbeat",
-    "Illumise",
-    "Roselia",
-    "Gulpin",
-    "Swalot",
-    "Carvanha",
-    "Sharpedo",
-    "Wailmer",
-    "Wailord",
-    "Numel",
-    "Camerupt",
-    "Torkoal",
-    "Spoink",
-    "Grumpig",
-    "Spinda",
-    "Trapinch",
-    "Vibrava",
-    "Flygon",
-    "Cacnea",
-    "Cacturne",
-    "Swablu",
-    "Altaria",
-    "Zangoose",
-    "Seviper",
-    "Lunatone",
-    "Solrock",
-    "Barboach",
-    "Whiscash",
-    "Corphish",
-    "Crawdaunt",
-    "Baltoy",
-    "Claydol",
-    "Lileep",
-    "Cradily",
-    "Anorith",
-    "Armaldo",
-    "Feebas",
-    "Milotic",
-    "Castform",
-    "Kecleon",
-    "Shuppet",
-    "Banette",
-    "Duskull",
-    "Dusclops",
-    "Tropius",
-    "Chimecho",
-    "Absol",
-    "Wynaut",
-    "Snorunt",
-    "Glalie",
-    "Spheal",
-    "Sealeo",
-    "Walrein",
-    "Clamperl",
-    "Huntail",
-    "Gorebyss",
-    "Relicanth",
-    "Luvdisc",
-    "Bagon",
-    "Shelgon",
-    "Salamence",
-    "Beldum",
-    "Metang",
-    "Metagross",
-    "Regirock",
-    "Regice",
-    "Registeel",
-    "Latias",
-    "Latios",
-    "Kyogre",
-    "Groudon",
-    "Rayquaza",
-    "Jirachi",
-    "Deoxys-Normal",
-    "Turtwig",
-    "Grotle",
-    "Torterra",
-    "Chimchar",
-    "Monferno",
-    "Infernape",
-    "Piplup",
-    "Prinplup",
-    "Empoleon",
-    "Starly",
-    "Staravia",
-    "Staraptor",
-    "Bidoof",
-    "Bibarel",
-    "Kricketot",
-    "Kricketune",
-    "Shinx",
-    "Luxio",
-    "Luxray",
-    "Budew",
-    "Roserade",
-    "Cranidos",
-    "Rampardos",
-    "Shieldon",
-    "Bastiodon",
-    "Burmy",
-    "Wormadam-Plant",
-    "Mothim",
-    "Combee",
-    "Vespiquen",
-    "Pachirisu",
-    "Buizel",
-    "Floatzel",
-    "Cherubi",
-    "Cherrim",
-    "Shellos",
-    "Gastrodon",
-    "Ambipom",
-    "Drifloon",
-    "Drifblim",
-    "Buneary",
-    "Lopunny",
-    "Mismagius",
-    "Honchkrow",
-    "Glameow",
-    "Purugly",
-    "Chingling",
-    "Stunky",
-    "Skuntank",
-    "Bronzor",
-    "Bronzong",
-    "Bonsly",
-    "Mime-Jr",
-    "Happiny",
-    "Chatot",
-    "Spiritomb",
-    "Gible",
-    "Gabite",
-    "Garchomp",
-    "Munchlax",
-    "Riolu",
-    "Lucario",
-    "Hippopotas",
-    "Hippowdon",
-    "Skorupi",
-    "Drapion",
-    "Croagunk",
-    "Toxicroak",
-    "Carnivine",
-    "Finneon",
-    "Lumineon",
-    "Mantyke",
-    "Snover",
-    "Abomasnow",
-    "Weavile",
-    "Magnezone",
-    "Lickilicky",
-    "Rhyperior",
-    "Tangrowth",
-    "Electivire",
-    "Magmortar",
-    "Togekiss",
-    "Yanmega",
-    "Leafeon",
-    "Glaceon",
-    "Gliscor",
-    "Mamoswine",
-    "Porygon-Z",
-    "Gallade",
-    "Probopass",
-    "Dusknoir",
-    "Froslass",
-    "Rotom",
-    "Uxie",
-    "Mesprit",
-    "Azelf",
-    "Dialga",
-    "Palkia",
-    "Heatran",
-    "Regigigas",
-    "Giratina-Altered",
-    "Cresselia",
-    "Phione",
-    "Manaphy",
-    "Darkrai",
-    "Shaymin-Land",
-    "Arceus",
-    "Victini",
-    "Snivy",
-    "Servine",
-    "Serperior",
-    "Tepig",
-    "Pignite",
-    "Emboar",
-    "Oshawott",
-    "Dewott",
-    "Samurott",
-    "Patrat",
-    "Watchog",
-    "Lillipup",
-    "Herdier",
-    "Stoutland",
-    "Purrloin",
-    "Liepard",
-    "Pansage",
-    "Simisage",
-    "Pansear",
-    "Simisear",
-    "Panpour",
-    "Simipour",
-    "Munna",
-    "Musharna",
-    "Pidove",
-    "Tranquill",
-    "Unfezant",
-    "Blitzle",
-    "Zebstrika",
-    "Roggenrola",
-    "Boldore",
-    "Gigalith",
-    "Woobat",
-    "Swoobat",
-    "Drilbur",
-    "Excadrill",
-    "Audino",
-    "Timburr",
-    "Gurdurr",
-    "Conkeldurr",
-    "Tympole",
-    "Palpitoad",
-    "Seismitoad",
-    "Throh",
-    "Sawk",
-    "Sewaddle",
-    "Swadloon",
-    "Leavanny",
-    "Venipede",
-    "Whirlipede",
-    "Scolipede",
-    "Cottonee",
-    "Whimsicott",
-    "Petilil",
-    "Lilligant",
-    "Basculin-Red-Striped",
-    "Sandile",
-    "Krokorok",
-    "Krookodile",
-    "Darumaka",
-    "Darmanitan",
-    "Maractus",
-    "Dwebble",
-    "Crustle",
-    "Scraggy",
-    "Scrafty",
-    "Sigilyph",
-    "Yamask",
-    "Cofagrigus",
-    "Tirtouga",
-    "Carracosta",
-    "Archen",
-    "Archeops",
-    "Trubbish",
-    "Garbodor",
-    "Zorua",
-    "Zoroark",
-    "Minccino",
-    "Cinccino",
-    "Gothita",
-    "Gothorita",
-    "Gothitelle",
-    "Solosis",
-    "Duosion",
-    "Reuniclus",
-    "Ducklett",
-    "Swanna",
-    "Vanillite",
-    "Vanillish",
-    "Vanilluxe",
-    "Deerling",
-    "Sawsbuck",
-    "Emolga",
-    "Karrablast",
-    "Escavalier",
-    "Foongus",
-    "Amoonguss",
-    "Frillish",
-    "Jellicent",
-    "Alomomola",
-    "Joltik",
-    "Galvantula",
-    "Ferroseed",
-    "Ferrothorn",
-    "Klink",
-    "Klang",
-    "Klinklang",
-    "Tynamo",
-    "Eelektrik",
-    "Eelektross",
-    "Elgyem",
-    "Beheeyem",
-    "Litwick",
-    "Lampent",
-    "Chandelure",
-    "Axew",
-    "Fraxure",
-    "Haxorus",
-    "Cubchoo",
-    "Beartic",
-    "Cryogonal",
-    "Shelmet",
-    "Accelgor",
-    "Stunfisk",
-    "Mienfoo",
-    "Mienshao",
-    "Druddigon",
-    "Golett",
-    "Golurk",
-    "Pawniard",
-    "Bisharp",
-    "Bouffalant",
-    "Rufflet",
-    "Braviary",
-    "Vullaby",
-    "Mandibuzz",
-    "Heatmor",
-    "Durant",
-    "Deino",
-    "Zweilous",
-    "Hydreigon",
-    "Larvesta",
-    "Volcarona",
-    "Cobalion",
-    "Terrakion",
-    "Virizion",
-    "Tornadus-Incarnate",
-    "Thundurus-Incarnate",
-    "Reshiram",
-    "Zekrom",
-    "Landorus-Incarnate",
-    "Kyurem",
-    "Keldeo-Ordinary",
-    "Meloetta-Aria",
-    "Genesect",
-    "Chespin",
-    "Quilladin",
-    "Chesnaught",
-    "Fennekin",
-    "Braixen",
-    "Delphox",
-    "Froakie",
-    "Frogadier",
-    "Greninja",
-    "Bunnelby",
-    "Diggersby",
-    "Fletchling",
-    "Fletchinder",
-    "Talonflame",
-    "Scatterbug",
-    "Spewpa",
-    "Vivillon",
-    "Litleo",
-    "Pyroar",
-    "Flabebe",
-    "Floette",
-    "Florges",
-    "Skiddo",
-    "Gogoat",
-    "Pancham",
-    "Pangoro",
-    "Furfrou",
-    "Espurr",
-    "Meowstic-Male",
-    "Honedge",
-    "Doublade",
-    "Aegislash-Shield",
-    "Spritzee",
-    "Aromatisse",
-    "Swirlix",
-    "Slurpuff",
-    "Inkay",
-    "Malamar",
-    "Binacle",
-    "Barbaracle",
-    "Skrelp",
-    "Dragalge",
-    "Clauncher",
-    "Clawitzer",
-    "Helioptile",
-    "Heliolisk",
-    "Tyrunt",
-    "Tyrantrum",
-    "Amaura",
-    "Aurorus",
-    "Sylveon",
-    "Hawlucha",
-    "Dedenne",
-    "Carbink",
-    "Goomy",
-    "Sliggoo",
-    "Goodra",
-    "Klefki",
-    "Phantump",
-    "Trevenant",
-    "Pumpkaboo-Average",
-    "Gourgeist-Average",
-    "Bergmite",
-    "Avalugg",
-    "Noibat",
-    "Noivern",
-    "Xerneas",
-    "Yveltal",
-    "Zygarde",
-    "Diancie",
-    "Hoopa",
-    "Volcanion",
-    "Deoxys-Attack",
-    "Deoxys-Defense",
-    "Deoxys-Speed",
-    "Wormadam-Sandy",
-    "Wormadam-Trash",
-    "Shaymin-Sky",
-    "Giratina-Origin",
-    "Rotom-Heat",
-    "Rotom-Wash",
-    "Rotom-Frost",
-    "Rotom-Fan",
-    "Rotom-Mow",
-    "Castform-Sunny",
-    "Castform-Rainy",
-    "Castform-Snowy",
-    "Basculin-Blue-Striped",
-    "Darmanitan-Zen",
-    "Meloetta-Pirouette",
-    "Tornadus-Therian",
-    "Thundurus-Therian",
-    "Landorus-Therian",
-    "Kyurem-Black",
-    "Kyurem-White",
-    "Keldeo-Resolute",
-    "Meowstic-Female",
-    "Aegislash-Blade",
-    "Pumpkaboo-Small",
-    "Pumpkaboo-Large",
-    "Pumpkaboo-Super",
-    "Gourgeist-Small",
-    "Gourgeist-Large",
-    "Gourgeist-Super",
-    "Venusaur-Mega",
-    "Charizard-Mega-X",
-    "Charizard-Mega-Y",
-    "Blastoise-Mega",
-    "Alakazam-Mega",
-    "Gengar-Mega",
-    "Kangaskhan-Mega",
-    "Pinsir-Mega",
-    "Gyarados-Mega",
-    "Aerodactyl-Mega",
-    "Mewtwo-Mega-X",
-    "Mewtwo-Mega-Y",
-    "Ampharos-Mega",
-    "Scizor-Mega",
-    "Heracross-Mega",
-    "Houndoom-Mega",
-    "Tyranitar-Mega",
-    "Blaziken-Mega",
-    "Gardevoir-Mega",
-    "Mawile-Mega",
-    "Aggron-Mega",
-    "Medicham-Mega",
-    "Manectric-Mega",
-    "Banette-Mega",
-    "Absol-Mega",
-    "Garchomp-Mega",
-    "Lucario-Mega",
-    "Abomasnow-Mega",
-    "Floette-Eternal",
-    "Latias-Mega",
-    "Latios-Mega",
-    "Swampert-Mega",
-    "Sceptile-Mega",
-    "Sableye-Mega",
-    "Altaria-Mega",
-    "Gallade-Mega",
-    "Audino-Mega",
-    "Sharpedo-Mega",
-    "Slowbro-Mega",
-    "Steelix-Mega",
-    "Pidgeot-Mega",
-    "Glalie-Mega",
-    "Diancie-Mega",
-    "Metagross-Mega",
-    "Kyogre-Primal",
-    "Groudon-Primal",
-    "Rayquaza-Mega",
-    "Pikachu-Rock-Star",
-    "Pikachu-Belle",
-    "Pikachu-Pop-Star",
-    "Pikachu-Phd",
-    "Pikachu-Libre",
-    "Pikachu-Cosplay",
-    "Hoopa-Unbound",
-    "Camerupt-Mega",
-    "Lopunny-Mega",
-    "Salamence-Mega",
-    "Beedrill-Mega",
-];
+/// Words to build names from, in one language.
+#[derive(Debug, Clone)]
+pub struct WordList {
+    pub adjectives: Vec<String>,
+    pub nouns: Vec<String>,
+    /// Put the adjective before the noun, as in English, or after it, as in French.
+    pub adjective_first: bool,
+}
+
+impl WordList {
+    fn new(adjectives: &[&str], nouns: &[&str], adjective_first: bool) -> Self {
+        Self {
+            adjectives: adjectives.iter().map(|word| word.to_string()).collect(),
+            nouns: nouns.iter().map(|word| word.to_string()).collect(),
+            adjective_first,
+        }
+    }
+
+    fn generate(&self, rng: &mut impl Rng) -> Option<String> {
+        let adjective = self.adjectives.choose(rng)?;
+        let noun = self.nouns.choose(rng)?;
+        Some(if self.adjective_first {
+            format!("{} {}", adjective, noun)
+        } else {
+            format!("{} {}", noun, adjective)
+        })
+    }
+}
+
+/// [`NameGenerator`] combining an adjective and a noun from word lists, grouped by theme then
+/// by locale.
+///
+/// Built-in themes are `animals` and `fantasy`, available in English (`en`), French (`fr`) and
+/// Spanish (`es`).
+pub struct WordLists {
+    themes: HashMap<String, HashMap<String, WordList>>,
+    default_theme: String,
+    default_locale: String,
+    numeric_suffix: bool,
+}
+
+impl WordLists {
+    /// The built-in themes, defaulting to `animals` in English.
+    pub fn builtin() -> Self {
+        let mut lists = Self {
+            themes: HashMap::new(),
+            default_theme: "animals".to_string(),
+            default_locale: "en".to_string(),
+            numeric_suffix: false,
+        };
+        for (theme, locale, adjectives, nouns, adjective_first) in [
+            ("animals", "en", ADJECTIVES, ANIMALS, true),
+            ("animals", "fr", ADJECTIVES_FR, ANIMALS_FR, false),
+            ("animals", "es", ADJECTIVES_ES, ANIMALS_ES, false),
+            ("fantasy", "en", ADJECTIVES, FANTASY, true),
+            ("fantasy", "fr", ADJECTIVES_FR, FANTASY_FR, false),
+            ("fantasy", "es", ADJECTIVES_ES, FANTASY_ES, false),
+        ] {
+            lists.add(
+                theme,
+                locale,
+                WordList::new(adjectives, nouns, adjective_first),
+            );
+        }
+        lists
+    }
+
+    /// The built-in themes, with the themes and defaults from the configuration. A theme from
+    /// the configuration replaces a built-in one with the same name and locale.
+    ///
+    /// Panics if the default theme doesn't exist.
+    pub fn from_settings(settings: &RandomNameSettings) -> Self {
+        let mut lists = Self::builtin();
+        for theme in &settings.themes {
+            let mut adjectives = words(&theme.adjectives);
+            if adjectives.is_empty() {
+                // reuse the built-in adjectives of the locale
+                adjectives = lists
+                    .themes
+                    .values()
+                    .find_map(|locales| locales.get(&theme.locale))
+                    .map(|list| list.adjectives.clone())
+                    .unwrap_or_default();
+            }
+            lists.add(
+                &theme.name,
+                &theme.locale,
+                WordList {
+                    adjectives,
+                    nouns: words(&theme.nouns),
+                    adjective_first: theme.adjective_first,
+                },
+            );
+        }
+        assert!(
+            lists.themes.contains_key(&settings.default_theme),
+            "unknown default name theme {}",
+            settings.default_theme
+        );
+        lists.default_theme = settings.default_theme.clone();
+        lists.default_locale = settings.default_locale.clone();
+        lists.numeric_suffix = settings.numeric_suffix;
+        lists
+    }
+
+    /// Add the words of a theme in a locale.
+    pub fn add(&mut self, theme: &str, locale: &str, words: WordList) {
+        self.themes
+            .entry(theme.to_string())
+            .or_default()
+            .insert(locale.to_lowercase(), words);
+    }
+
+    /// Find the words for a theme and a locale. An unknown theme falls back to the default
+    /// theme, and a locale missing from the theme to its language (`fr` for `fr-CA`), then to
+    /// the default locale, then to any locale of the theme.
+    fn words(&self, theme: Option<&str>, locale: Option<&str>) -> Option<&WordList> {
+        let locales = theme
+            .and_then(|theme| self.themes.get(theme))
+            .or_else(|| self.themes.get(&self.default_theme))?;
+        let locale = locale.map(str::to_lowercase);
+        locale
+            .as_deref()
+            .and_then(|locale| {
+                locales
+                    .get(locale)
+                    .or_else(|| locales.get(locale.split(['-', '_']).next()?))
+            })
+            .or_else(|| locales.get(&self.default_locale))
+            .or_else(|| {
+                let mut available = locales.keys().collect::<Vec<_>>();
+                available.sort();
+                locales.get(*available.first()?)
+            })
+    }
+}
+
+impl NameGenerator for WordLists {
+    fn generate(&self, theme: Option<&str>, locale: Option<&str>) -> String {
+        let mut rng = rand::thread_rng();
+        let name = self
+            .words(theme, locale)
+            .and_then(|words| words.generate(&mut rng))
+            .unwrap_or_else(|| "Player".to_string());
+        if self.numeric_suffix {
+            format!("{} {}", name, rng.gen_range(1..100))
+        } else {
+            name
+        }
+    }
+}
+
+/// Read a list with one word per line, ignoring empty lines.
+fn words(list: &str) -> Vec<String> {
+    list.lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_string())
+        .collect()
+}
 
 const ADJECTIVES: &[&str] = &[
     "Acidic",
@@ -1285,3 +634,571 @@ const ADJECTIVES: &[&str] = &[
     "Zany",
     "Zealous",
 ];
+
+const ANIMALS: &[&str] = &[
+    "Aardvark",
+    "Albatross",
+    "Alligator",
+    "Alpaca",
+    "Antelope",
+    "Armadillo",
+    "Badger",
+    "Beaver",
+    "Bison",
+    "Buffalo",
+    "Butterfly",
+    "Camel",
+    "Capybara",
+    "Caribou",
+    "Cat",
+    "Chameleon",
+    "Cheetah",
+    "Chinchilla",
+    "Cobra",
+    "Cougar",
+    "Coyote",
+    "Crab",
+    "Crane",
+    "Crocodile",
+    "Crow",
+    "Deer",
+    "Dingo",
+    "Dolphin",
+    "Donkey",
+    "Dove",
+    "Dragonfly",
+    "Duck",
+    "Eagle",
+    "Eel",
+    "Elephant",
+    "Elk",
+    "Emu",
+    "Falcon",
+    "Ferret",
+    "Flamingo",
+    "Fox",
+    "Frog",
+    "Gazelle",
+    "Gecko",
+    "Gerbil",
+    "Giraffe",
+    "Goat",
+    "Goose",
+    "Gorilla",
+    "Hamster",
+    "Hare",
+    "Hawk",
+    "Hedgehog",
+    "Heron",
+    "Hippo",
+    "Hornet",
+    "Horse",
+    "Hummingbird",
+    "Hyena",
+    "Ibis",
+    "Iguana",
+    "Impala",
+    "Jackal",
+    "Jaguar",
+    "Jellyfish",
+    "Kangaroo",
+    "Kingfisher",
+    "Koala",
+    "Lemur",
+    "Leopard",
+    "Lion",
+    "Lizard",
+    "Llama",
+    "Lobster",
+    "Lynx",
+    "Macaw",
+    "Magpie",
+    "Manatee",
+    "Meerkat",
+    "Mole",
+    "Mongoose",
+    "Moose",
+    "Moth",
+    "Narwhal",
+    "Newt",
+    "Octopus",
+    "Okapi",
+    "Opossum",
+    "Orca",
+    "Ostrich",
+    "Otter",
+    "Owl",
+    "Panda",
+    "Panther",
+    "Parrot",
+    "Peacock",
+    "Pelican",
+    "Penguin",
+    "Pigeon",
+    "Platypus",
+    "Porcupine",
+    "Puffin",
+    "Puma",
+    "Quail",
+    "Quokka",
+    "Rabbit",
+    "Raccoon",
+    "Raven",
+    "Reindeer",
+    "Rhino",
+    "Salamander",
+    "Salmon",
+    "Seal",
+    "Shark",
+    "Sheep",
+    "Sloth",
+    "Snail",
+    "Sparrow",
+    "Squid",
+    "Squirrel",
+    "Starfish",
+    "Stork",
+    "Swan",
+    "Tapir",
+    "Tiger",
+    "Toad",
+    "Toucan",
+    "Turtle",
+    "Vulture",
+    "Walrus",
+    "Weasel",
+    "Whale",
+    "Wolf",
+    "Wombat",
+    "Woodpecker",
+    "Yak",
+    "Zebra",
+];
+
+const FANTASY: &[&str] = &[
+    "Alchemist",
+    "Archer",
+    "Banshee",
+    "Bard",
+    "Basilisk",
+    "Berserker",
+    "Centaur",
+    "Chimera",
+    "Cleric",
+    "Cyclops",
+    "Djinn",
+    "Dragon",
+    "Druid",
+    "Dwarf",
+    "Elf",
+    "Enchanter",
+    "Fairy",
+    "Gargoyle",
+    "Giant",
+    "Gnome",
+    "Goblin",
+    "Golem",
+    "Griffin",
+    "Guardian",
+    "Harpy",
+    "Hydra",
+    "Imp",
+    "Knight",
+    "Kraken",
+    "Leprechaun",
+    "Mage",
+    "Mermaid",
+    "Minotaur",
+    "Monk",
+    "Necromancer",
+    "Nymph",
+    "Ogre",
+    "Oracle",
+    "Paladin",
+    "Pegasus",
+    "Phoenix",
+    "Pixie",
+    "Ranger",
+    "Rogue",
+    "Sorcerer",
+    "Sphinx",
+    "Sprite",
+    "Titan",
+    "Troll",
+    "Unicorn",
+    "Valkyrie",
+    "Warlock",
+    "Werewolf",
+    "Witch",
+    "Wizard",
+    "Wyvern",
+    "Yeti",
+];
+
+/// French adjectives with the same form in masculine and feminine, to agree with any noun.
+const ADJECTIVES_FR: &[&str] = &[
+    "Agile",
+    "Aimable",
+    "Autonome",
+    "Bizarre",
+    "Brave",
+    "Calme",
+    "Candide",
+    "Capable",
+    "Célèbre",
+    "Comique",
+    "Cosmique",
+    "Drôle",
+    "Dynamique",
+    "Efficace",
+    "Énergique",
+    "Énorme",
+    "Étrange",
+    "Exemplaire",
+    "Fantastique",
+    "Féroce",
+    "Fiable",
+    "Fidèle",
+    "Flexible",
+    "Formidable",
+    "Fragile",
+    "Habile",
+    "Héroïque",
+    "Honnête",
+    "Humble",
+    "Immense",
+    "Impassible",
+    "Incroyable",
+    "Intrépide",
+    "Invincible",
+    "Libre",
+    "Logique",
+    "Lucide",
+    "Magique",
+    "Magnifique",
+    "Mystique",
+    "Noble",
+    "Optimiste",
+    "Pacifique",
+    "Paisible",
+    "Pratique",
+    "Rapide",
+    "Rebelle",
+    "Redoutable",
+    "Robuste",
+    "Romantique",
+    "Sage",
+    "Sensible",
+    "Serviable",
+    "Simple",
+    "Sincère",
+    "Solide",
+    "Souple",
+    "Stable",
+    "Sublime",
+    "Superbe",
+    "Sympathique",
+    "Tenace",
+    "Terrible",
+    "Timide",
+    "Tranquille",
+    "Unique",
+    "Utile",
+    "Valable",
+    "Vaste",
+    "Véloce",
+    "Volubile",
+];
+
+const ANIMALS_FR: &[&str] = &[
+    "Abeille",
+    "Aigle",
+    "Alpaga",
+    "Antilope",
+    "Baleine",
+    "Blaireau",
+    "Bison",
+    "Caméléon",
+    "Capybara",
+    "Castor",
+    "Chameau",
+    "Chat",
+    "Chouette",
+    "Cigogne",
+    "Colibri",
+    "Crabe",
+    "Crocodile",
+    "Cygne",
+    "Dauphin",
+    "Dromadaire",
+    "Écureuil",
+    "Éléphant",
+    "Escargot",
+    "Faucon",
+    "Flamant",
+    "Fourmi",
+    "Furet",
+    "Gazelle",
+    "Girafe",
+    "Gorille",
+    "Grenouille",
+    "Guépard",
+    "Hérisson",
+    "Hibou",
+    "Hippocampe",
+    "Hirondelle",
+    "Jaguar",
+    "Kangourou",
+    "Koala",
+    "Lama",
+    "Lapin",
+    "Lémurien",
+    "Léopard",
+    "Lézard",
+    "Libellule",
+    "Lion",
+    "Loup",
+    "Loutre",
+    "Lynx",
+    "Manchot",
+    "Marmotte",
+    "Méduse",
+    "Mouette",
+    "Narval",
+    "Orque",
+    "Ours",
+    "Panda",
+    "Panthère",
+    "Papillon",
+    "Paresseux",
+    "Pélican",
+    "Perroquet",
+    "Phoque",
+    "Pieuvre",
+    "Pingouin",
+    "Puma",
+    "Raton",
+    "Renard",
+    "Requin",
+    "Rhinocéros",
+    "Salamandre",
+    "Sanglier",
+    "Suricate",
+    "Tapir",
+    "Tigre",
+    "Tortue",
+    "Toucan",
+    "Vautour",
+    "Zèbre",
+];
+
+const FANTASY_FR: &[&str] = &[
+    "Alchimiste",
+    "Archère",
+    "Barde",
+    "Basilic",
+    "Centaure",
+    "Chevalier",
+    "Chimère",
+    "Cyclope",
+    "Djinn",
+    "Dragon",
+    "Druide",
+    "Elfe",
+    "Enchanteur",
+    "Fée",
+    "Gargouille",
+    "Géant",
+    "Gnome",
+    "Gobelin",
+    "Golem",
+    "Griffon",
+    "Gardien",
+    "Harpie",
+    "Hydre",
+    "Kraken",
+    "Licorne",
+    "Lutin",
+    "Mage",
+    "Magicienne",
+    "Minotaure",
+    "Moine",
+    "Nain",
+    "Nécromancien",
+    "Nymphe",
+    "Ogre",
+    "Oracle",
+    "Paladin",
+    "Pégase",
+    "Phénix",
+    "Rôdeur",
+    "Sirène",
+    "Sorcier",
+    "Sorcière",
+    "Sphinx",
+    "Titan",
+    "Troll",
+    "Valkyrie",
+    "Vouivre",
+    "Yéti",
+];
+
+/// Spanish adjectives with the same form in masculine and feminine, to agree with any noun.
+const ADJECTIVES_ES: &[&str] = &[
+    "Admirable",
+    "Agradable",
+    "Alegre",
+    "Amable",
+    "Audaz",
+    "Brillante",
+    "Capaz",
+    "Cortés",
+    "Elegante",
+    "Eficaz",
+    "Enorme",
+    "Especial",
+    "Estelar",
+    "Feliz",
+    "Feroz",
+    "Fiel",
+    "Formidable",
+    "Fuerte",
+    "Genial",
+    "Gentil",
+    "Grande",
+    "Humilde",
+    "Imparable",
+    "Increíble",
+    "Inmortal",
+    "Invencible",
+    "Joven",
+    "Leal",
+    "Libre",
+    "Noble",
+    "Paciente",
+    "Potente",
+    "Puntual",
+    "Real",
+    "Rebelde",
+    "Sensible",
+    "Sutil",
+    "Tenaz",
+    "Terrible",
+    "Valiente",
+    "Veloz",
+    "Vital",
+    "Voraz",
+];
+
+const ANIMALS_ES: &[&str] = &[
+    "Abeja",
+    "Águila",
+    "Alpaca",
+    "Ardilla",
+    "Ballena",
+    "Búho",
+    "Caballo",
+    "Camaleón",
+    "Cangrejo",
+    "Capibara",
+    "Castor",
+    "Cebra",
+    "Ciervo",
+    "Cigüeña",
+    "Cisne",
+    "Cocodrilo",
+    "Colibrí",
+    "Conejo",
+    "Coyote",
+    "Delfín",
+    "Elefante",
+    "Erizo",
+    "Flamenco",
+    "Foca",
+    "Gacela",
+    "Gato",
+    "Gorila",
+    "Guepardo",
+    "Halcón",
+    "Hipopótamo",
+    "Hormiga",
+    "Iguana",
+    "Jaguar",
+    "Jirafa",
+    "Koala",
+    "Lagarto",
+    "Lechuza",
+    "León",
+    "Leopardo",
+    "Lince",
+    "Llama",
+    "Lobo",
+    "Loro",
+    "Mapache",
+    "Mariposa",
+    "Medusa",
+    "Mono",
+    "Nutria",
+    "Orca",
+    "Oso",
+    "Panda",
+    "Pantera",
+    "Pelícano",
+    "Perezoso",
+    "Pingüino",
+    "Pulpo",
+    "Puma",
+    "Rana",
+    "Suricata",
+    "Tapir",
+    "Tiburón",
+    "Tigre",
+    "Tortuga",
+    "Tucán",
+    "Zorro",
+];
+
+const FANTASY_ES: &[&str] = &[
+    "Alquimista",
+    "Arquera",
+    "Bardo",
+    "Basilisco",
+    "Bruja",
+    "Brujo",
+    "Caballero",
+    "Centauro",
+    "Cíclope",
+    "Dragón",
+    "Druida",
+    "Duende",
+    "Elfo",
+    "Enano",
+    "Esfinge",
+    "Fénix",
+    "Gárgola",
+    "Gigante",
+    "Gnomo",
+    "Goblin",
+    "Golem",
+    "Grifo",
+    "Guardián",
+    "Hada",
+    "Hechicero",
+    "Hidra",
+    "Kraken",
+    "Mago",
+    "Minotauro",
+    "Monje",
+    "Nigromante",
+    "Ninfa",
+    "Ogro",
+    "Oráculo",
+    "Paladín",
+    "Pegaso",
+    "Sirena",
+    "Titán",
+    "Trol",
+    "Unicornio",
+    "Valquiria",
+    "Yeti",
+];
@@ -0,0 +1,43 @@
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Characters of recovery codes, from Crockford's Base32: there is no I, L, O or U that could be
+/// misread.
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Number of characters in a code, without the dashes.
+const LENGTH: usize = 12;
+/// Number of characters between dashes.
+const GROUP_LENGTH: usize = 4;
+
+/// Generate a recovery code, formatted like `ABCD-EFGH-JKMN`.
+pub fn generate() -> String {
+    let mut rng = rand::thread_rng();
+    let mut code = String::new();
+    for i in 0..LENGTH {
+        if i > 0 && i % GROUP_LENGTH == 0 {
+            code.push('-');
+        }
+        code.push(ALPHABET[rng.gen_range(0..ALPHABET.len())] as char);
+    }
+    code
+}
+
+/// Get the hash of a recovery code, as it's stored. Case, dashes and spaces are ignored, and
+/// letters that look like a digit are read as that digit.
+///
+/// Returns `None` if the code can't be a recovery code.
+pub fn hash(code: &str) -> Option<Vec<u8>> {
+    let normalized = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect::<String>();
+    if normalized.len() != LENGTH || !normalized.bytes().all(|c| ALPHABET.contains(&c)) {
+        return None;
+    }
+    Some(Sha256::digest(normalized.as_bytes()).to_vec())
+}
@@ -10,7 +10,7 @@ async fn not_authenticated() {
     let client = reqwest::Client::new();
 
     let response = client
-        .get(&format!("{}/api/v1/admin/whoami", app.address))
+        .get(format!("{}/api/v1/admin/whoami", app.address))
         .send()
         .await
         .expect("Failed to execute request.");
@@ -30,7 +30,7 @@ async fn get_test_token() {
     let client = reqwest::Client::new();
 
     let response = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -52,7 +52,7 @@ async fn use_test_token() {
     let client = reqwest::Client::new();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -64,7 +64,7 @@ async fn use_test_token() {
         .expect("got body");
 
     let response = client
-        .get(&format!("{}/api/v1/admin/whoami", app.address))
+        .get(format!("{}/api/v1/admin/whoami", app.address))
         .bearer_auth(token.token)
         .send()
         .await
@@ -0,0 +1,192 @@
+use jornet_server::domains::{
+    leaderboard::Leaderboard,
+    moderation::{BanInput, BanKind, PlayerBan},
+    player::{Player, PlayerAuth},
+    score::ScoreInput,
+};
+use reqwest::StatusCode;
+
+mod helper;
+
+async fn ban(
+    app: &helper::TestApp,
+    token: &str,
+    leaderboard: &Leaderboard,
+    player: &Player,
+    kind: BanKind,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .put(format!(
+            "{}/api/v1/leaderboards/{}/moderation/bans/{}",
+            app.address, leaderboard.id, player.id
+        ))
+        .bearer_auth(token)
+        .json(&BanInput { kind })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// Query to view the scores as `player`.
+fn viewer(player: &Player) -> String {
+    let auth = PlayerAuth::new(player);
+    format!(
+        "?player={}&timestamp={}&k={}",
+        player.id, auth.timestamp, auth.k
+    )
+}
+
+async fn get_public_scores(
+    app: &helper::TestApp,
+    leaderboard: &Leaderboard,
+    query: &str,
+) -> Vec<f32> {
+    #[derive(serde::Deserialize)]
+    struct PublicScore {
+        score: f32,
+    }
+    reqwest::Client::new()
+        .get(format!(
+            "{}/api/v1/scores/{}{}",
+            app.address, leaderboard.id, query
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<PublicScore>>()
+        .await
+        .expect("valid scores")
+        .into_iter()
+        .map(|score| score.score)
+        .collect()
+}
+
+#[tokio::test]
+async fn banned_player_cant_send_scores() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let cheater = helper::create_player(&app, "cheater").await;
+
+    let response = ban(&app, &token, &leaderboard, &cheater, BanKind::Ban).await;
+    assert!(response.status().is_success());
+
+    let response = client
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&ScoreInput::new(
+            1000.0,
+            cheater.clone(),
+            None,
+            leaderboard.key,
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(response, reqwest::StatusCode::FORBIDDEN, "player_banned").await;
+
+    let other_leaderboard = helper::create_leaderboard(&app, &token).await;
+    helper::send_score(&app, &other_leaderboard, &cheater, 1000.0).await;
+
+    let response = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}/moderation/bans/{}",
+            app.address, leaderboard.id, cheater.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    helper::send_score(&app, &leaderboard, &cheater, 10.0).await;
+}
+
+#[tokio::test]
+async fn shadow_banned_player_only_sees_their_scores() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let cheater = helper::create_player(&app, "cheater").await;
+    let player = helper::create_player(&app, "player").await;
+
+    let response = ban(&app, &token, &leaderboard, &cheater, BanKind::Shadow).await;
+    assert!(response.status().is_success());
+
+    helper::send_score(&app, &leaderboard, &cheater, 1000.0).await;
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+
+    assert_eq!(get_public_scores(&app, &leaderboard, "").await, vec![10.0]);
+    assert_eq!(
+        get_public_scores(&app, &leaderboard, &viewer(&player)).await,
+        vec![10.0]
+    );
+    assert_eq!(
+        get_public_scores(&app, &leaderboard, &viewer(&cheater)).await,
+        vec![1000.0, 10.0]
+    );
+
+    let around = |query: String| {
+        reqwest::Client::new()
+            .get(format!(
+                "{}/api/v1/scores/{}/around/{}{}",
+                app.address, leaderboard.id, cheater.id, query
+            ))
+            .send()
+    };
+    let response = around(viewer(&cheater))
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+    let response = around(String::new())
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(response, StatusCode::NOT_FOUND, "player_not_ranked").await;
+
+    // only the shadow banned player can see their scores
+    let forged = Player {
+        key: player.key,
+        ..cheater.clone()
+    };
+    for query in [format!("?player={}", cheater.id), viewer(&forged)] {
+        let response = reqwest::Client::new()
+            .get(format!(
+                "{}/api/v1/scores/{}{}",
+                app.address, leaderboard.id, query
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        helper::assert_error(response, StatusCode::UNAUTHORIZED, "invalid_signature").await;
+    }
+
+    let bans = reqwest::Client::new()
+        .get(format!(
+            "{}/api/v1/leaderboards/{}/moderation/bans",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<PlayerBan>>()
+        .await
+        .expect("valid bans");
+    assert_eq!(bans.len(), 1);
+    assert_eq!(bans[0].player, cheater.id);
+    assert_eq!(bans[0].kind, BanKind::Shadow);
+}
+
+#[tokio::test]
+async fn cant_ban_on_leaderboard_of_other_admin() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    let other_token = helper::admin_token(&app).await;
+
+    let response = ban(&app, &other_token, &leaderboard, &player, BanKind::Ban).await;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+}
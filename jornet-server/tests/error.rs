@@ -0,0 +1,107 @@
+use jornet_server::domains::{leaderboard::LeaderboardInput, score::ScoreInput};
+
+mod helper;
+
+#[tokio::test]
+async fn invalid_body_is_a_client_error() {
+    let app = helper::spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/players", app.address))
+        .header("Content-Type", "application/json")
+        .body("{\"name\": 5}")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "invalid_request",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_path_is_a_client_error() {
+    let app = helper::spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/v1/scores/not-a-uuid", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "invalid_request",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn missing_token_is_unauthorized() {
+    let app = helper::spawn_app().await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/leaderboards", app.address))
+        .json(&LeaderboardInput {
+            name: "my leaderboard".to_string(),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/leaderboards", app.address))
+        .bearer_auth("not a token")
+        .json(&LeaderboardInput {
+            name: "my leaderboard".to_string(),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(response, reqwest::StatusCode::UNAUTHORIZED, "unauthorized").await;
+}
+
+#[tokio::test]
+async fn database_errors_are_internal_errors() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    sqlx::query("DROP TABLE leaderboards CASCADE")
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+
+    // a failing query is not mistaken for a missing leaderboard
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+        "internal_error",
+    )
+    .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&ScoreInput::new(10.0, player, None, leaderboard.key))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+        "internal_error",
+    )
+    .await;
+}
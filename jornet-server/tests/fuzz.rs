@@ -0,0 +1,219 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use uuid::Uuid;
+
+use jornet_server::domains::score::ScoreInput;
+
+mod helper;
+
+/// A worker that panics drops the connection, so the request fails instead of returning a
+/// response.
+async fn assert_handled(request: reqwest::RequestBuilder, description: &str) {
+    let response = request
+        .send()
+        .await
+        .unwrap_or_else(|err| panic!("no response for {}: {}", description, err));
+    assert!(
+        !response.status().is_server_error(),
+        "server error {} for {}",
+        response.status(),
+        description
+    );
+}
+
+fn json_post(client: &reqwest::Client, url: &str, body: &str) -> reqwest::RequestBuilder {
+    client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+}
+
+#[tokio::test]
+async fn malformed_scores_are_rejected() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    let url = format!("{}/api/v1/scores/{}", app.address, leaderboard.id);
+
+    let valid = ScoreInput::new(10.0, player.clone(), None, leaderboard.key);
+    let with = |field: &str, value: &str| {
+        let mut fields = [
+            ("score", "10.0".to_string()),
+            ("player", format!("\"{}\"", valid.player)),
+            ("meta", "null".to_string()),
+            ("timestamp", valid.timestamp.to_string()),
+            ("k", format!("\"{}\"", valid.k)),
+            ("version", valid.version.to_string()),
+        ];
+        for (name, current) in fields.iter_mut() {
+            if *name == field {
+                *current = value.to_string();
+            }
+        }
+        format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|(name, value)| format!("\"{}\":{}", name, value))
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    };
+
+    let long_meta = format!("\"{}\"", "a".repeat(100_000));
+    let long_k = format!("\"{}\"", "ab".repeat(10_000));
+    let bodies = vec![
+        "".to_string(),
+        "{}".to_string(),
+        "[]".to_string(),
+        "null".to_string(),
+        "\"score\"".to_string(),
+        "{\"score\":".to_string(),
+        with("k", "\"zz\""),
+        with("k", "\"abc\""),
+        with("k", "\"\""),
+        with("k", "12"),
+        with("k", &long_k),
+        with("timestamp", "18446744073709551615"),
+        with("timestamp", "9223372036854775807"),
+        with("timestamp", "300000000000"),
+        with("timestamp", "-1"),
+        with("timestamp", "1e20"),
+        with("score", "\"10\""),
+        with("score", "null"),
+        with("score", "1e39"),
+        with("score", "-1e39"),
+        with("player", "\"not-a-uuid\""),
+        with("player", &format!("\"{}\"", Uuid::new_v4())),
+        with("meta", "\"\\u0000\""),
+        with("meta", &long_meta),
+        with("meta", "{}"),
+        with("version", "0"),
+        with("version", "256"),
+        with("version", "\"2\""),
+    ];
+    for body in bodies {
+        assert_handled(
+            json_post(&client, &url, &body),
+            &format!("score body {:.100}", body),
+        )
+        .await;
+    }
+
+    // random mutations of a valid body
+    let mut rng = StdRng::seed_from_u64(0);
+    let valid_body = with("score", "10.0");
+    for _ in 0..200 {
+        let mut body = valid_body.clone().into_bytes();
+        for _ in 0..rng.gen_range(1..5) {
+            let position = rng.gen_range(0..body.len());
+            body[position] = rng.gen_range(0x20..0x7f);
+        }
+        let body = String::from_utf8(body).unwrap();
+        assert_handled(
+            json_post(&client, &url, &body),
+            &format!("score body {}", body),
+        )
+        .await;
+    }
+
+    for path in [
+        "not-a-uuid".to_string(),
+        format!("{}/around/not-a-uuid", leaderboard.id),
+        format!("{}/seasons/99999999999", leaderboard.id),
+        format!("{}/seasons/-1", leaderboard.id),
+        format!("{}/seasons/abc", leaderboard.id),
+    ] {
+        assert_handled(
+            client.get(format!("{}/api/v1/scores/{}", app.address, path)),
+            &format!("path {}", path),
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn malformed_queries_are_rejected() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+
+    let queries = [
+        "limit=-5",
+        "limit=99999999999999999999",
+        "limit=abc",
+        "offset=-1",
+        "offset=9223372036854775807",
+        "order=sideways",
+        "period=yesterday",
+        "utc_offset=2147483647",
+        "utc_offset=-2147483648",
+        "utc_offset=99999999999",
+        "since=18446744073709551615",
+        "since=9223372036854775807",
+        "since=300000000000",
+        "until=0",
+        "until=-1",
+        "player=abc",
+        "timestamp=-1",
+        "k=zz&timestamp=0",
+        "count=-1",
+        "count=9223372036854775807",
+        "period=month&utc_offset=-1439",
+        "%%%",
+    ];
+    for query in queries {
+        for path in [
+            format!("{}", leaderboard.id),
+            format!("{}/around/{}", leaderboard.id, player.id),
+            format!("{}/seasons/1", leaderboard.id),
+        ] {
+            assert_handled(
+                client.get(format!("{}/api/v1/scores/{}?{}", app.address, path, query)),
+                &format!("query {} on {}", query, path),
+            )
+            .await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn malformed_players_and_logins_are_rejected() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let long_name = format!("{{\"name\":\"{}\"}}", "a".repeat(100_000));
+    for body in [
+        "",
+        "{",
+        "[]",
+        "{\"name\":5}",
+        "{\"name\":\"\\u0000\"}",
+        long_name.as_str(),
+    ] {
+        assert_handled(
+            json_post(&client, &format!("{}/api/v1/players", app.address), body),
+            &format!("player body {:.100}", body),
+        )
+        .await;
+    }
+
+    for body in ["", "{}", "{\"uuid\":\"abc\"}", "{\"uuid\":5}"] {
+        assert_handled(
+            json_post(&client, &format!("{}/oauth/by_uuid", app.address), body),
+            &format!("login body {}", body),
+        )
+        .await;
+    }
+
+    let response = client
+        .get(format!("{}/health_check", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+}
@@ -1,6 +1,20 @@
+#![allow(dead_code)]
+
 use std::net::TcpListener;
 
-use jornet_server::configuration::{get_configuration, DatabaseSettings};
+use jornet_server::{
+    configuration::{get_configuration, DatabaseSettings, Settings},
+    domains::{
+        admin::TokenReply,
+        leaderboard::{Leaderboard, LeaderboardInput},
+        player::{Player, PlayerInput},
+        score::ScoreInput,
+    },
+    error::ErrorBody,
+    player_name::{NameFilter, NameValidator},
+    random_name::WordLists,
+};
+use serde::Serialize;
 use sqlx::{Connection, Executor, PgConnection, PgPool};
 use uuid::Uuid;
 
@@ -19,7 +33,52 @@ pub async fn spawn_app() -> TestApp {
 
     let server =
         jornet_server::run(listener, connection_pool.clone()).expect("Failed to bind address");
-    let _ = tokio::spawn(server);
+    tokio::spawn(server);
+
+    TestApp {
+        address: format!("http://127.0.0.1:{}", port),
+        db_pool: connection_pool,
+    }
+}
+
+pub async fn spawn_app_with_name_filter(filter: impl NameFilter + 'static) -> TestApp {
+    spawn_app_with(|config| {
+        (
+            NameValidator::new(config.player_names.clone(), filter),
+            WordLists::from_settings(&config.random_names),
+        )
+    })
+    .await
+}
+
+pub async fn spawn_app_with_random_names(random_names: WordLists) -> TestApp {
+    spawn_app_with(|config| {
+        (
+            NameValidator::from_settings(&config.player_names),
+            random_names,
+        )
+    })
+    .await
+}
+
+async fn spawn_app_with(plugins: impl FnOnce(&Settings) -> (NameValidator, WordLists)) -> TestApp {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
+    let port = listener.local_addr().unwrap().port();
+
+    let mut configuration = get_configuration();
+    configuration.database.database_name = Uuid::new_v4().to_string();
+    let connection_pool = configure_database(&configuration.database).await;
+
+    let (names, random_names) = plugins(&configuration);
+    let server = jornet_server::run_with(
+        listener,
+        connection_pool.clone(),
+        configuration,
+        names,
+        random_names,
+    )
+    .expect("Failed to bind address");
+    tokio::spawn(server);
 
     TestApp {
         address: format!("http://127.0.0.1:{}", port),
@@ -46,3 +105,110 @@ pub async fn configure_database(config: &DatabaseSettings) -> PgPool {
         .expect("Failed to migrate the database");
     connection_pool
 }
+
+#[derive(Serialize)]
+struct UuidInput {
+    uuid: Uuid,
+}
+
+pub async fn admin_token(app: &TestApp) -> String {
+    reqwest::Client::new()
+        .post(format!("{}/oauth/by_uuid", app.address))
+        .json(&UuidInput {
+            uuid: Uuid::new_v4(),
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<TokenReply>()
+        .await
+        .expect("got body")
+        .token
+}
+
+pub async fn create_leaderboard(app: &TestApp, token: &str) -> Leaderboard {
+    create_leaderboard_with(
+        app,
+        token,
+        &LeaderboardInput {
+            name: "my leaderboard".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+pub async fn create_leaderboard_with(
+    app: &TestApp,
+    token: &str,
+    leaderboard: &LeaderboardInput,
+) -> Leaderboard {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/leaderboards", app.address))
+        .bearer_auth(token)
+        .json(leaderboard)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Leaderboard>()
+        .await
+        .expect("valid leaderboard")
+}
+
+pub async fn create_player(app: &TestApp, name: &str) -> Player {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput {
+            name: Some(name.to_string()),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Player>()
+        .await
+        .expect("valid player")
+}
+
+pub async fn send_score(app: &TestApp, leaderboard: &Leaderboard, player: &Player, score: f32) {
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&ScoreInput::new(
+            score,
+            player.clone(),
+            None,
+            leaderboard.key,
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+}
+
+/// Insert a score directly in the database, to control its timestamp.
+pub async fn insert_score(
+    app: &TestApp,
+    leaderboard: &Leaderboard,
+    player: &Player,
+    score: f32,
+    timestamp: u64,
+) {
+    sqlx::query!(
+        "INSERT INTO scores (id, leaderboard, score, player, timestamp) VALUES ($1, $2, $3, $4, TO_TIMESTAMP($5))",
+        Uuid::new_v4(),
+        leaderboard.id,
+        score,
+        player.id,
+        timestamp as f64
+    )
+    .execute(&app.db_pool)
+    .await
+    .expect("Failed to insert score.");
+}
+
+/// Check that a response is an error with the expected status and code.
+pub async fn assert_error(response: reqwest::Response, status: reqwest::StatusCode, code: &str) {
+    assert_eq!(response.status(), status);
+    let body = response.json::<ErrorBody>().await.expect("valid error");
+    assert_eq!(body.code, code);
+}
@@ -1,4 +1,8 @@
-use jornet_server::domains::{admin::TokenReply, leaderboard::LeaderboardInput};
+use jornet_server::domains::{
+    admin::TokenReply,
+    leaderboard::{Leaderboard, LeaderboardInput, LeaderboardUpdate, ScoreMode},
+    score::ScoreInput,
+};
 use serde::Serialize;
 use uuid::Uuid;
 
@@ -15,7 +19,7 @@ async fn create_leaderboard() {
     let client = reqwest::Client::new();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -27,10 +31,11 @@ async fn create_leaderboard() {
         .expect("got body");
 
     let response = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -38,3 +43,184 @@ async fn create_leaderboard() {
 
     assert!(response.status().is_success());
 }
+
+#[tokio::test]
+async fn update_leaderboard() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+
+    let updated = client
+        .put(format!(
+            "{}/api/v1/leaderboards/{}",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .json(&LeaderboardUpdate {
+            name: Some("renamed".to_string()),
+            score_mode: Some(ScoreMode::Best),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Leaderboard>()
+        .await
+        .expect("valid leaderboard");
+
+    assert_eq!(updated.id, leaderboard.id);
+    assert_eq!(updated.key, leaderboard.key);
+    assert_eq!(updated.name, "renamed");
+    assert_eq!(updated.sort_order, leaderboard.sort_order);
+    assert_eq!(updated.score_mode, ScoreMode::Best);
+}
+
+#[tokio::test]
+async fn rotate_leaderboard_key() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    let rotated = client
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/key",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Leaderboard>()
+        .await
+        .expect("valid leaderboard");
+    assert_ne!(rotated.key, leaderboard.key);
+
+    let response = client
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&ScoreInput::new(
+            10.0,
+            player.clone(),
+            None,
+            leaderboard.key,
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(!response.status().is_success());
+
+    helper::send_score(&app, &rotated, &player, 10.0).await;
+}
+
+#[tokio::test]
+async fn delete_leaderboard() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+    sqlx::query!(
+        "INSERT INTO player_bans (leaderboard, player, kind) VALUES ($1, $2, 'shadow')",
+        leaderboard.id,
+        player.id
+    )
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+
+    let response = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    let leaderboards = client
+        .get(format!("{}/api/v1/leaderboards", app.address))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .text()
+        .await
+        .expect("valid leaderboards");
+    assert_eq!(leaderboards, "[]");
+
+    let scores = sqlx::query!(
+        "SELECT COUNT(*) as count FROM scores WHERE leaderboard = $1",
+        leaderboard.id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .expect("Failed to count scores.");
+    assert_eq!(scores.count, Some(0));
+    let bans = sqlx::query!(
+        "SELECT COUNT(*) as count FROM player_bans WHERE leaderboard = $1",
+        leaderboard.id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .expect("Failed to count bans.");
+    assert_eq!(bans.count, Some(0));
+}
+
+#[tokio::test]
+async fn cant_change_leaderboard_of_other_admin() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let other_token = helper::admin_token(&app).await;
+
+    let response = client
+        .put(format!(
+            "{}/api/v1/leaderboards/{}",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&other_token)
+        .json(&LeaderboardUpdate {
+            name: Some("renamed".to_string()),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = client
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/key",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&other_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&other_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    helper::send_score(
+        &app,
+        &leaderboard,
+        &helper::create_player(&app, "player").await,
+        10.0,
+    )
+    .await;
+}
@@ -0,0 +1,215 @@
+use jornet_server::domains::{
+    leaderboard::Leaderboard,
+    moderation::{ModeratedScores, ScoreDetails},
+};
+
+mod helper;
+
+async fn get_score_details(
+    app: &helper::TestApp,
+    token: &str,
+    leaderboard: &Leaderboard,
+    query: &str,
+) -> Vec<ScoreDetails> {
+    reqwest::Client::new()
+        .get(format!(
+            "{}/api/v1/leaderboards/{}/moderation/scores{}",
+            app.address, leaderboard.id, query
+        ))
+        .bearer_auth(token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<ScoreDetails>>()
+        .await
+        .expect("valid scores")
+}
+
+async fn get_public_scores(app: &helper::TestApp, leaderboard: &Leaderboard) -> Vec<f32> {
+    #[derive(serde::Deserialize)]
+    struct PublicScore {
+        score: f32,
+    }
+    reqwest::Client::new()
+        .get(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<PublicScore>>()
+        .await
+        .expect("valid scores")
+        .into_iter()
+        .map(|score| score.score)
+        .collect()
+}
+
+#[tokio::test]
+async fn list_score_details() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player1 = helper::create_player(&app, "player1").await;
+    let player2 = helper::create_player(&app, "player2").await;
+    helper::send_score(&app, &leaderboard, &player1, 10.0).await;
+    helper::send_score(&app, &leaderboard, &player2, 20.0).await;
+
+    let scores = get_score_details(&app, &token, &leaderboard, "").await;
+    assert_eq!(scores.len(), 2);
+    assert!(scores.iter().all(|score| score.deleted_at.is_none()));
+
+    let scores = get_score_details(
+        &app,
+        &token,
+        &leaderboard,
+        &format!("?player={}", player2.id),
+    )
+    .await;
+    assert_eq!(scores.len(), 1);
+    assert_eq!(scores[0].player, player2.id);
+    assert_eq!(scores[0].name, "player2");
+    assert_eq!(scores[0].score, 20.0);
+}
+
+#[tokio::test]
+async fn delete_and_restore_score() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+    helper::send_score(&app, &leaderboard, &player, 1000.0).await;
+
+    let cheated = get_score_details(&app, &token, &leaderboard, "")
+        .await
+        .into_iter()
+        .find(|score| score.score == 1000.0)
+        .unwrap();
+
+    let response = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}/moderation/scores/{}",
+            app.address, leaderboard.id, cheated.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    assert_eq!(get_public_scores(&app, &leaderboard).await, vec![10.0]);
+    let deleted = get_score_details(&app, &token, &leaderboard, "?deleted=true").await;
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].id, cheated.id);
+    assert!(deleted[0].deleted_at.is_some());
+
+    let response = client
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/moderation/scores/{}/restore",
+            app.address, leaderboard.id, cheated.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    assert_eq!(
+        get_public_scores(&app, &leaderboard).await,
+        vec![1000.0, 10.0]
+    );
+}
+
+#[tokio::test]
+async fn purge_and_restore_player() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let cheater = helper::create_player(&app, "cheater").await;
+    let player = helper::create_player(&app, "player").await;
+    helper::send_score(&app, &leaderboard, &cheater, 1000.0).await;
+    helper::send_score(&app, &leaderboard, &cheater, 2000.0).await;
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+
+    let purged = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}/moderation/players/{}",
+            app.address, leaderboard.id, cheater.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<ModeratedScores>()
+        .await
+        .expect("valid result");
+    assert_eq!(purged.scores, 2);
+    assert_eq!(get_public_scores(&app, &leaderboard).await, vec![10.0]);
+
+    let restored = client
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/moderation/players/{}/restore",
+            app.address, leaderboard.id, cheater.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<ModeratedScores>()
+        .await
+        .expect("valid result");
+    assert_eq!(restored.scores, 2);
+    assert_eq!(
+        get_public_scores(&app, &leaderboard).await,
+        vec![2000.0, 1000.0, 10.0]
+    );
+}
+
+#[tokio::test]
+async fn cant_moderate_leaderboard_of_other_admin() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    helper::send_score(&app, &leaderboard, &player, 10.0).await;
+    let score = &get_score_details(&app, &token, &leaderboard, "").await[0];
+
+    let other_token = helper::admin_token(&app).await;
+
+    let response = client
+        .get(format!(
+            "{}/api/v1/leaderboards/{}/moderation/scores",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&other_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}/moderation/scores/{}",
+            app.address, leaderboard.id, score.id
+        ))
+        .bearer_auth(&other_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = client
+        .delete(format!(
+            "{}/api/v1/leaderboards/{}/moderation/players/{}",
+            app.address, leaderboard.id, player.id
+        ))
+        .bearer_auth(&other_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    assert_eq!(get_public_scores(&app, &leaderboard).await, vec![10.0]);
+}
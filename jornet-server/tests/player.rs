@@ -1,23 +1,25 @@
-use jornet_server::domains::player::{Player, PlayerInput};
-use serde::Serialize;
+use jornet_server::{
+    configuration::{NameThemeSettings, RandomNameSettings},
+    domains::player::{Player, PlayerInput, PlayerUpdate},
+    player_name::NameFilter,
+    random_name::{WordList, WordLists},
+};
+use reqwest::StatusCode;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 mod helper;
 
-#[derive(Serialize)]
-struct UuidInput {
-    uuid: Uuid,
-}
-
 #[tokio::test]
 async fn create_player() {
     let app = helper::spawn_app().await;
     let client = reqwest::Client::new();
 
     let response = client
-        .post(&format!("{}/api/v1/players", app.address))
+        .post(format!("{}/api/v1/players", app.address))
         .json(&PlayerInput {
             name: Some("hello".to_string()),
+            ..Default::default()
         })
         .send()
         .await
@@ -33,8 +35,8 @@ async fn create_player_with_random_name() {
     let client = reqwest::Client::new();
 
     let response = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.");
@@ -43,3 +45,287 @@ async fn create_player_with_random_name() {
     let player: Player = response.json().await.unwrap();
     assert!(!player.name.is_empty());
 }
+
+#[tokio::test]
+async fn rename_player() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let player = helper::create_player(&app, "Zany Wartortle").await;
+
+    let response = client
+        .put(format!("{}/api/v1/players/{}", app.address, player.id))
+        .json(&PlayerUpdate::new(&player, "renamed".to_string()))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(response.status().is_success());
+    let renamed: Player = response.json().await.unwrap();
+    assert_eq!(renamed.id, player.id);
+    assert_eq!(renamed.key, player.key);
+    assert_eq!(renamed.name, "renamed");
+    let saved = sqlx::query!("SELECT name FROM players WHERE id = $1", player.id)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(saved.name, "renamed");
+}
+
+#[tokio::test]
+async fn rename_player_rejected() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let player = helper::create_player(&app, "player").await;
+    let url = format!("{}/api/v1/players/{}", app.address, player.id);
+    let now = OffsetDateTime::now_utc().unix_timestamp() as u64;
+
+    let other = Player {
+        key: Uuid::new_v4(),
+        ..player.clone()
+    };
+    for (update, status, code) in [
+        (
+            PlayerUpdate::new(&other, "renamed".to_string()),
+            StatusCode::UNAUTHORIZED,
+            "invalid_signature",
+        ),
+        (
+            PlayerUpdate::new_at(&player, "renamed".to_string(), now - 3600),
+            StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        ),
+        (
+            PlayerUpdate::new(&player, " ".to_string()),
+            StatusCode::BAD_REQUEST,
+            "name_empty",
+        ),
+        (
+            PlayerUpdate::new(&player, "a".repeat(33)),
+            StatusCode::BAD_REQUEST,
+            "name_too_long",
+        ),
+        (
+            PlayerUpdate::new(&player, "null\0byte".to_string()),
+            StatusCode::BAD_REQUEST,
+            "name_invalid_characters",
+        ),
+    ] {
+        let response = client.put(&url).json(&update).send().await.unwrap();
+        helper::assert_error(response, status, code).await;
+    }
+
+    let response = client
+        .put(format!("{}/api/v1/players/{}", app.address, Uuid::new_v4()))
+        .json(&PlayerUpdate::new(&player, "renamed".to_string()))
+        .send()
+        .await
+        .unwrap();
+    helper::assert_error(response, StatusCode::NOT_FOUND, "unknown_player").await;
+
+    // replaying an update, or sending an older one, doesn't revert the name
+    let first = PlayerUpdate::new_at(&player, "first".to_string(), now - 10);
+    let second = PlayerUpdate::new_at(&player, "second".to_string(), now);
+    for update in [&first, &second] {
+        let response = client.put(&url).json(update).send().await.unwrap();
+        assert!(response.status().is_success());
+    }
+    for update in [&first, &second] {
+        let response = client.put(&url).json(update).send().await.unwrap();
+        helper::assert_error(response, StatusCode::CONFLICT, "stale_player_update").await;
+    }
+}
+
+async fn post_player(app: &helper::TestApp, name: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput {
+            name: Some(name.to_string()),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn player_names_are_normalized() {
+    let app = helper::spawn_app().await;
+
+    for (name, expected) in [
+        ("  Zany   Wartortle ", "Zany Wartortle"),
+        ("tab\tand\nnewline", "tab and newline"),
+        // "e" followed by a combining acute accent is composed into "é"
+        ("Cafe\u{301}", "Caf\u{e9}"),
+        ("Jürgen-O'Brien!", "Jürgen-O'Brien!"),
+        ("日本語の名前", "日本語の名前"),
+    ] {
+        let response = post_player(&app, name).await;
+        assert!(response.status().is_success(), "{:?}", name);
+        let player: Player = response.json().await.unwrap();
+        assert_eq!(player.name, expected);
+    }
+}
+
+#[tokio::test]
+async fn invalid_player_names_are_rejected() {
+    let app = helper::spawn_app().await;
+
+    for (name, code) in [
+        ("", "name_empty"),
+        ("   ", "name_empty"),
+        (&"a".repeat(33), "name_too_long"),
+        ("<script>", "name_invalid_characters"),
+        ("null\0byte", "name_invalid_characters"),
+        ("emoji 🎮", "name_invalid_characters"),
+        ("shit happens", "name_blocked"),
+        ("Sh1t Happens", "name_blocked"),
+        ("F.u_Ck", "name_blocked"),
+        ("f u c k", "name_blocked"),
+        ("Bullshit", "name_blocked"),
+        ("motherfucker", "name_blocked"),
+    ] {
+        helper::assert_error(post_player(&app, name).await, StatusCode::BAD_REQUEST, code).await;
+    }
+
+    // blocked words only match whole words, unless the list says otherwise
+    for name in [
+        "Alert Watchog",
+        "Adventurous Hitmonlee",
+        "a b c",
+        "Scunthorpe",
+        "Matsushita",
+        "Shitake",
+    ] {
+        assert!(post_player(&app, name).await.status().is_success());
+    }
+}
+
+struct NoBosses;
+
+impl NameFilter for NoBosses {
+    fn is_allowed(&self, name: &str) -> bool {
+        !name.to_lowercase().contains("boss")
+    }
+}
+
+#[tokio::test]
+async fn custom_name_filter() {
+    let app = helper::spawn_app_with_name_filter(NoBosses).await;
+
+    helper::assert_error(
+        post_player(&app, "The Boss").await,
+        StatusCode::BAD_REQUEST,
+        "name_blocked",
+    )
+    .await;
+    // the filter replaces the configured blocklist
+    assert!(post_player(&app, "shit happens")
+        .await
+        .status()
+        .is_success());
+}
+
+async fn post_random_player(app: &helper::TestApp, theme: &str, locale: &str) -> Player {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput {
+            name: None,
+            theme: Some(theme.to_string()),
+            locale: Some(locale.to_string()),
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .expect("valid player")
+}
+
+fn word_list(adjectives: &[&str], nouns: &[&str], adjective_first: bool) -> WordList {
+    WordList {
+        adjectives: adjectives.iter().map(|word| word.to_string()).collect(),
+        nouns: nouns.iter().map(|word| word.to_string()).collect(),
+        adjective_first,
+    }
+}
+
+#[tokio::test]
+async fn random_name_with_theme_and_locale() {
+    let mut random_names = WordLists::builtin();
+    random_names.add("forest", "en", word_list(&["Quick"], &["Fox"], true));
+    random_names.add("forest", "fr", word_list(&["Rapide"], &["Renard"], false));
+    let app = helper::spawn_app_with_random_names(random_names).await;
+
+    assert_eq!(
+        post_random_player(&app, "forest", "fr").await.name,
+        "Renard Rapide"
+    );
+    // falls back to the language, then to the default locale. The name is taken, so a number
+    // is added
+    assert!(post_random_player(&app, "forest", "fr-CA")
+        .await
+        .name
+        .starts_with("Renard Rapide "));
+    assert_eq!(
+        post_random_player(&app, "forest", "de").await.name,
+        "Quick Fox"
+    );
+    // an unknown theme falls back to the default one
+    let player = post_random_player(&app, "unknown", "en").await;
+    assert_eq!(player.name.split(' ').count(), 2);
+}
+
+#[tokio::test]
+async fn random_names_are_unique() {
+    let mut random_names = WordLists::builtin();
+    random_names.add("lonely", "en", word_list(&["Lone"], &["Wolf"], true));
+    let app = helper::spawn_app_with_random_names(random_names).await;
+
+    // names chosen by players are not given either
+    let mut names = vec![helper::create_player(&app, "Lone Wolf").await.name];
+    for _ in 0..3 {
+        let name = post_random_player(&app, "lonely", "en").await.name;
+        assert!(name.starts_with("Lone Wolf "), "{}", name);
+        assert!(!names.contains(&name), "{} given twice", name);
+        names.push(name);
+    }
+}
+
+#[tokio::test]
+async fn random_names_are_validated() {
+    let mut random_names = WordLists::builtin();
+    random_names.add(
+        "rude",
+        "en",
+        word_list(&["Shitty", "Lone", "Grey", "Old"], &["Wolf"], true),
+    );
+    let app = helper::spawn_app_with_random_names(random_names).await;
+
+    for _ in 0..5 {
+        let name = post_random_player(&app, "rude", "en").await.name;
+        assert!(!name.starts_with("Shitty"), "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn random_names_keep_a_single_number() {
+    let random_names = WordLists::from_settings(&RandomNameSettings {
+        default_theme: "lonely".to_string(),
+        default_locale: "en".to_string(),
+        numeric_suffix: true,
+        themes: vec![NameThemeSettings {
+            name: "lonely".to_string(),
+            locale: "en".to_string(),
+            adjectives: "Lone".to_string(),
+            nouns: "Wolf".to_string(),
+            adjective_first: true,
+        }],
+    });
+    let app = helper::spawn_app_with_random_names(random_names).await;
+
+    // once the numbers from the generator are used up, a bigger one replaces them
+    for _ in 0..120 {
+        let name = post_random_player(&app, "lonely", "en").await.name;
+        assert_eq!(name.split(' ').count(), 3, "{}", name);
+    }
+}
@@ -0,0 +1,171 @@
+use jornet_server::domains::{
+    leaderboard::{LeaderboardInput, SortOrder},
+    player::{Player, PlayerAuth, PlayerProfile, ProfileQuery, ScoreHistoryOrder},
+};
+use reqwest::StatusCode;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+mod helper;
+
+fn query(auth: PlayerAuth) -> ProfileQuery {
+    ProfileQuery {
+        timestamp: auth.timestamp,
+        k: auth.k,
+        leaderboard: None,
+        order: ScoreHistoryOrder::Recent,
+        limit: None,
+        offset: None,
+    }
+}
+
+async fn get_profile(
+    app: &helper::TestApp,
+    player: Uuid,
+    query: &ProfileQuery,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!("{}/api/v1/players/{}/profile", app.address, player))
+        .query(query)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn get_own_profile_and_scores() {
+    let app = helper::spawn_app().await;
+
+    let token = helper::admin_token(&app).await;
+    let highest = helper::create_leaderboard(&app, &token).await;
+    let lowest = helper::create_leaderboard_with(
+        &app,
+        &token,
+        &LeaderboardInput {
+            name: "speedrun".to_string(),
+            sort_order: SortOrder::Ascending,
+            ..Default::default()
+        },
+    )
+    .await;
+    let player = helper::create_player(&app, "alice").await;
+    let other = helper::create_player(&app, "bob").await;
+    helper::insert_score(&app, &highest, &player, 10.0, 1_000).await;
+    helper::insert_score(&app, &lowest, &player, 50.0, 2_000).await;
+    helper::insert_score(&app, &highest, &player, 30.0, 3_000).await;
+    helper::insert_score(&app, &lowest, &player, 40.0, 4_000).await;
+    helper::insert_score(&app, &highest, &other, 100.0, 5_000).await;
+
+    let response = get_profile(&app, player.id, &query(PlayerAuth::new(&player))).await;
+    assert!(response.status().is_success());
+    let profile: PlayerProfile = response.json().await.unwrap();
+    assert_eq!(profile.id, player.id);
+    assert_eq!(profile.name, "alice");
+    assert!(OffsetDateTime::parse(
+        &profile.created_at,
+        &time::format_description::well_known::Rfc3339
+    )
+    .is_ok());
+    assert_eq!(
+        profile
+            .scores
+            .iter()
+            .map(|s| (s.leaderboard, s.score))
+            .collect::<Vec<_>>(),
+        vec![
+            (lowest.id, 40.0),
+            (highest.id, 30.0),
+            (lowest.id, 50.0),
+            (highest.id, 10.0),
+        ]
+    );
+
+    let profile: PlayerProfile = get_profile(
+        &app,
+        player.id,
+        &ProfileQuery {
+            leaderboard: Some(highest.id),
+            ..query(PlayerAuth::new(&player))
+        },
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    assert_eq!(
+        profile.scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![30.0, 10.0]
+    );
+
+    // best scores first, following the sort order of each leaderboard
+    let profile: PlayerProfile = get_profile(
+        &app,
+        player.id,
+        &ProfileQuery {
+            order: ScoreHistoryOrder::Best,
+            ..query(PlayerAuth::new(&player))
+        },
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let mut best = profile
+        .scores
+        .chunks(2)
+        .map(|chunk| (chunk[0].leaderboard, chunk[0].score, chunk[1].score))
+        .collect::<Vec<_>>();
+    best.sort_by_key(|(leaderboard, _, _)| *leaderboard == lowest.id);
+    assert_eq!(
+        best,
+        vec![(highest.id, 30.0, 10.0), (lowest.id, 40.0, 50.0)]
+    );
+
+    let profile: PlayerProfile = get_profile(
+        &app,
+        player.id,
+        &ProfileQuery {
+            limit: Some(1),
+            offset: Some(1),
+            ..query(PlayerAuth::new(&player))
+        },
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    assert_eq!(
+        profile.scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![30.0]
+    );
+}
+
+#[tokio::test]
+async fn profile_requires_player_key() {
+    let app = helper::spawn_app().await;
+    let player = helper::create_player(&app, "alice").await;
+    let now = OffsetDateTime::now_utc().unix_timestamp() as u64;
+
+    let other = Player {
+        key: Uuid::new_v4(),
+        ..player.clone()
+    };
+    for (auth, status, code) in [
+        (
+            PlayerAuth::new(&other),
+            StatusCode::UNAUTHORIZED,
+            "invalid_signature",
+        ),
+        (
+            PlayerAuth::new_at(&player, now - 3600),
+            StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        ),
+    ] {
+        let response = get_profile(&app, player.id, &query(auth)).await;
+        helper::assert_error(response, status, code).await;
+    }
+
+    let response = get_profile(&app, Uuid::new_v4(), &query(PlayerAuth::new(&player))).await;
+    helper::assert_error(response, StatusCode::NOT_FOUND, "unknown_player").await;
+}
@@ -0,0 +1,303 @@
+use jornet_server::domains::player::{
+    NewPlayer, Player, PlayerAuth, PlayerInput, PlayerMerge, PlayerProfile, ProfileQuery,
+    RecoveryInput, ScoreHistoryOrder,
+};
+use reqwest::StatusCode;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+mod helper;
+
+async fn create_new_player(app: &helper::TestApp, name: &str) -> NewPlayer {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput {
+            name: Some(name.to_string()),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .expect("valid player")
+}
+
+async fn recover(app: &helper::TestApp, recovery_code: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/players/recover", app.address))
+        .json(&RecoveryInput {
+            recovery_code: recovery_code.to_string(),
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn merge(app: &helper::TestApp, player: Uuid, merge: &PlayerMerge) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/players/{}/merge", app.address, player))
+        .json(merge)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn recover_player_with_code() {
+    let app = helper::spawn_app().await;
+    let created = create_new_player(&app, "alice").await;
+    assert_eq!(created.recovery_code.len(), 14);
+
+    // case and dashes don't matter
+    let typed = created.recovery_code.to_lowercase().replace('-', "");
+    let response = recover(&app, &typed).await;
+    assert!(response.status().is_success());
+    let recovered: NewPlayer = response.json().await.unwrap();
+    assert_eq!(recovered.player, created.player);
+    assert_ne!(recovered.recovery_code, created.recovery_code);
+
+    // a code can only be used once
+    for code in [
+        created.recovery_code.as_str(),
+        "not a code",
+        "0000-0000-0000",
+    ] {
+        let response = recover(&app, code).await;
+        helper::assert_error(response, StatusCode::UNAUTHORIZED, "invalid_recovery_code").await;
+    }
+    let response = recover(&app, &recovered.recovery_code).await;
+    assert!(response.status().is_success());
+
+    // random names come with a code too
+    let created: NewPlayer = reqwest::Client::new()
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let recovered: NewPlayer = recover(&app, &created.recovery_code)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(recovered.player, created.player);
+}
+
+#[tokio::test]
+async fn replace_recovery_code() {
+    let app = helper::spawn_app().await;
+    let created = create_new_player(&app, "alice").await;
+    let now = OffsetDateTime::now_utc().unix_timestamp() as u64;
+
+    let url = format!(
+        "{}/api/v1/players/{}/recovery_code",
+        app.address, created.player.id
+    );
+    let other = Player {
+        key: Uuid::new_v4(),
+        ..created.player.clone()
+    };
+    for (auth, status, code) in [
+        (
+            PlayerAuth::new(&other),
+            StatusCode::UNAUTHORIZED,
+            "invalid_signature",
+        ),
+        (
+            PlayerAuth::new_at(&created.player, now - 3600),
+            StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        ),
+    ] {
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&auth)
+            .send()
+            .await
+            .unwrap();
+        helper::assert_error(response, status, code).await;
+    }
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .json(&PlayerAuth::new(&created.player))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    let replaced: NewPlayer = response.json().await.unwrap();
+    assert_eq!(replaced.player, created.player);
+
+    let response = recover(&app, &created.recovery_code).await;
+    helper::assert_error(response, StatusCode::UNAUTHORIZED, "invalid_recovery_code").await;
+    let response = recover(&app, &replaced.recovery_code).await;
+    assert!(response.status().is_success());
+}
+
+#[tokio::test]
+async fn merge_players_combines_scores() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let (other_leaderboard, banned_leaderboard) = (Uuid::new_v4(), Uuid::new_v4());
+    let phone = helper::create_player(&app, "alice").await;
+    let desktop = helper::create_player(&app, "alice on desktop").await;
+    helper::insert_score(&app, &leaderboard, &phone, 10.0, 1_000).await;
+    helper::insert_score(&app, &leaderboard, &desktop, 20.0, 2_000).await;
+    for (leaderboard, player, kind) in [
+        (other_leaderboard, &desktop, "shadow"),
+        (banned_leaderboard, &phone, "shadow"),
+        (banned_leaderboard, &desktop, "ban"),
+    ] {
+        sqlx::query!(
+            "INSERT INTO player_bans (leaderboard, player, kind) VALUES ($1, $2, $3)",
+            leaderboard,
+            player.id,
+            kind
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let response = merge(&app, phone.id, &PlayerMerge::new(&phone, &desktop)).await;
+    assert!(response.status().is_success());
+    let merged: Player = response.json().await.unwrap();
+    assert_eq!(merged, phone);
+
+    let auth = PlayerAuth::new(&phone);
+    let profile: PlayerProfile = reqwest::Client::new()
+        .get(format!(
+            "{}/api/v1/players/{}/profile",
+            app.address, phone.id
+        ))
+        .query(&ProfileQuery {
+            timestamp: auth.timestamp,
+            k: auth.k,
+            leaderboard: None,
+            order: ScoreHistoryOrder::Best,
+            limit: None,
+            offset: None,
+        })
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        profile.scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![20.0, 10.0]
+    );
+
+    let mut bans = sqlx::query!("SELECT leaderboard, player, kind FROM player_bans")
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.leaderboard, r.player, r.kind))
+        .collect::<Vec<_>>();
+    bans.sort();
+    let mut expected = vec![
+        (other_leaderboard, phone.id, "shadow".to_string()),
+        (banned_leaderboard, phone.id, "ban".to_string()),
+    ];
+    expected.sort();
+    assert_eq!(bans, expected);
+
+    // the merged player is gone
+    let response = merge(&app, desktop.id, &PlayerMerge::new(&desktop, &phone)).await;
+    helper::assert_error(response, StatusCode::NOT_FOUND, "unknown_player").await;
+}
+
+async fn standings(app: &helper::TestApp, leaderboard: Uuid) -> Vec<(i64, Uuid, String, f32)> {
+    sqlx::query!(
+        "SELECT rank, player, name, score FROM season_standings WHERE leaderboard = $1 ORDER BY rank",
+        leaderboard
+    )
+    .fetch_all(&app.db_pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|r| (r.rank, r.player, r.name, r.score))
+    .collect()
+}
+
+#[tokio::test]
+async fn merge_players_keeps_closed_seasons() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let phone = helper::create_player(&app, "alice").await;
+    let desktop = helper::create_player(&app, "alice on desktop").await;
+    helper::send_score(&app, &leaderboard, &phone, 10.0).await;
+    helper::send_score(&app, &leaderboard, &desktop, 20.0).await;
+    let response = reqwest::Client::new()
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/seasons",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    let closed = standings(&app, leaderboard.id).await;
+    assert_eq!(closed.len(), 2);
+
+    let response = merge(&app, phone.id, &PlayerMerge::new(&phone, &desktop)).await;
+    assert!(response.status().is_success());
+
+    assert_eq!(standings(&app, leaderboard.id).await, closed);
+}
+
+#[tokio::test]
+async fn merge_requires_both_keys() {
+    let app = helper::spawn_app().await;
+    let player = helper::create_player(&app, "alice").await;
+    let other = helper::create_player(&app, "bob").await;
+    let now = OffsetDateTime::now_utc().unix_timestamp() as u64;
+
+    let forged = Player {
+        key: Uuid::new_v4(),
+        ..other.clone()
+    };
+    for (merge_request, status, code) in [
+        (
+            PlayerMerge::new(&player, &forged),
+            StatusCode::UNAUTHORIZED,
+            "invalid_signature",
+        ),
+        (
+            PlayerMerge::new_at(&player, &other, now - 3600),
+            StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        ),
+        (
+            PlayerMerge::new(&player, &player),
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+        ),
+    ] {
+        let response = merge(&app, player.id, &merge_request).await;
+        helper::assert_error(response, status, code).await;
+    }
+
+    let response = merge(
+        &app,
+        player.id,
+        &PlayerMerge::new(
+            &player,
+            &Player {
+                id: Uuid::new_v4(),
+                ..other.clone()
+            },
+        ),
+    )
+    .await;
+    helper::assert_error(response, StatusCode::NOT_FOUND, "unknown_player").await;
+}
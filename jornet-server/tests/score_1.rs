@@ -26,7 +26,7 @@ async fn save_score_unknown_player() {
     };
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -38,10 +38,11 @@ async fn save_score_unknown_player() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -51,13 +52,13 @@ async fn save_score_unknown_player() {
         .expect("valid leaderboard");
 
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&ScoreInput::new(543.21, player, None, leaderboard.key))
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert!(response.status().is_server_error());
+    helper::assert_error(response, reqwest::StatusCode::NOT_FOUND, "unknown_player").await;
 }
 
 #[tokio::test]
@@ -66,8 +67,8 @@ async fn save_score_wrong_key() {
     let client = reqwest::Client::new();
 
     let mut player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -78,7 +79,7 @@ async fn save_score_wrong_key() {
     player.key = Uuid::new_v4();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -90,10 +91,11 @@ async fn save_score_wrong_key() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -103,13 +105,18 @@ async fn save_score_wrong_key() {
         .expect("valid leaderboard");
 
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&ScoreInput::new(543.21, player, None, leaderboard.key))
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert!(response.status().is_server_error());
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "invalid_signature",
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -118,8 +125,8 @@ async fn save_score_wrong_leaderboard_key() {
     let client = reqwest::Client::new();
 
     let player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -128,7 +135,7 @@ async fn save_score_wrong_leaderboard_key() {
         .unwrap();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -140,10 +147,11 @@ async fn save_score_wrong_leaderboard_key() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -153,11 +161,16 @@ async fn save_score_wrong_leaderboard_key() {
         .expect("valid leaderboard");
 
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&ScoreInput::new(543.21, player, None, Uuid::new_v4()))
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert!(response.status().is_server_error());
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "invalid_signature",
+    )
+    .await;
 }
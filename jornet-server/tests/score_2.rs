@@ -20,8 +20,8 @@ async fn save_score() {
     let client = reqwest::Client::new();
 
     let player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -30,7 +30,7 @@ async fn save_score() {
         .unwrap();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -42,10 +42,11 @@ async fn save_score() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -55,7 +56,7 @@ async fn save_score() {
         .expect("valid leaderboard");
 
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&ScoreInput::new(543.21, player, None, leaderboard.key))
         .send()
         .await
@@ -70,8 +71,8 @@ async fn save_score_to_missing_dashboard() {
     let client = reqwest::Client::new();
 
     let player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -80,13 +81,18 @@ async fn save_score_to_missing_dashboard() {
         .unwrap();
 
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, Uuid::new_v4()))
+        .post(format!("{}/api/v1/scores/{}", app.address, Uuid::new_v4()))
         .json(&ScoreInput::new(543.21, player, None, Uuid::new_v4()))
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert!(response.status().is_server_error());
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::NOT_FOUND,
+        "unknown_leaderboard",
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -95,8 +101,8 @@ async fn save_score_wrong_hmac() {
     let client = reqwest::Client::new();
 
     let player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -105,7 +111,7 @@ async fn save_score_wrong_hmac() {
         .unwrap();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput { uuid: player.id })
         .send()
         .await
@@ -115,10 +121,11 @@ async fn save_score_wrong_hmac() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -131,11 +138,16 @@ async fn save_score_wrong_hmac() {
     score.k = "a5c825056477825c755cac22aff7c4ab".to_string();
 
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&score)
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert!(response.status().is_server_error());
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "invalid_signature",
+    )
+    .await;
 }
@@ -1,3 +1,4 @@
+use jornet_protocol::{SignatureVersion, SignedScore};
 use jornet_server::domains::{
     admin::TokenReply,
     leaderboard::{Leaderboard, LeaderboardInput},
@@ -20,8 +21,8 @@ async fn save_score_wrong_timestamp() {
     let client = reqwest::Client::new();
 
     let player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -30,7 +31,7 @@ async fn save_score_wrong_timestamp() {
         .unwrap();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -42,10 +43,11 @@ async fn save_score_wrong_timestamp() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -57,13 +59,18 @@ async fn save_score_wrong_timestamp() {
     let mut score = ScoreInput::new(543.21, player, None, leaderboard.key);
     score.timestamp = 5120;
     let response = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&score)
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert!(response.status().is_server_error());
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "invalid_signature",
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -72,8 +79,8 @@ async fn save_score_repeat_score() {
     let client = reqwest::Client::new();
 
     let player = client
-        .post(&format!("{}/api/v1/players", app.address))
-        .json(&PlayerInput { name: None })
+        .post(format!("{}/api/v1/players", app.address))
+        .json(&PlayerInput::default())
         .send()
         .await
         .expect("Failed to execute request.")
@@ -82,7 +89,7 @@ async fn save_score_repeat_score() {
         .unwrap();
 
     let token = client
-        .post(&format!("{}/oauth/by_uuid", app.address))
+        .post(format!("{}/oauth/by_uuid", app.address))
         .json(&UuidInput {
             uuid: Uuid::new_v4(),
         })
@@ -94,10 +101,11 @@ async fn save_score_repeat_score() {
         .expect("got body");
 
     let leaderboard = client
-        .post(&format!("{}/api/v1/leaderboards", app.address))
+        .post(format!("{}/api/v1/leaderboards", app.address))
         .bearer_auth(token.token)
         .json(&LeaderboardInput {
             name: "my leaderboard".to_string(),
+            ..Default::default()
         })
         .send()
         .await
@@ -108,18 +116,112 @@ async fn save_score_repeat_score() {
 
     let score = ScoreInput::new(543.21, player, None, leaderboard.key);
     let response1 = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&score)
         .send()
         .await
         .expect("Failed to execute request.");
     let response2 = client
-        .post(&format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
         .json(&score)
         .send()
         .await
         .expect("Failed to execute request.");
 
     assert!(response1.status().is_success());
-    assert!(response2.status().is_server_error());
+    helper::assert_error(response2, reqwest::StatusCode::CONFLICT, "duplicate_score").await;
+}
+
+#[tokio::test]
+async fn save_score_signed_with_v1() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    let mut score = ScoreInput::new(
+        543.21,
+        player.clone(),
+        Some("meta".to_string()),
+        leaderboard.key,
+    );
+    score.version = SignatureVersion::V1.as_u8();
+    score.k = SignedScore {
+        leaderboard_key: leaderboard.key,
+        player: player.id,
+        score: score.score,
+        meta: score.meta.as_deref(),
+        timestamp: score.timestamp,
+    }
+    .sign(SignatureVersion::V1, player.key);
+
+    // clients from before versioned signatures don't send a version
+    let response = client
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .header("Content-Type", "application/json")
+        .body(format!(
+            r#"{{"score":543.21,"player":"{}","meta":"meta","timestamp":{},"k":"{}"}}"#,
+            score.player, score.timestamp, score.k
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    score.score = 12.0;
+    score.k = SignedScore {
+        leaderboard_key: leaderboard.key,
+        player: player.id,
+        score: score.score,
+        meta: score.meta.as_deref(),
+        timestamp: score.timestamp,
+    }
+    .sign(SignatureVersion::V1, player.key);
+    let response = client
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&score)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    // a v1 signature is not valid as v2
+    score.version = SignatureVersion::V2.as_u8();
+    let response = client
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&score)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "invalid_signature",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn save_score_unknown_signature_version() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    let mut score = ScoreInput::new(543.21, player, None, leaderboard.key);
+    score.version = 42;
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&score)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "unsupported_signature_version",
+    )
+    .await;
 }
@@ -0,0 +1,169 @@
+use jornet_server::domains::{
+    leaderboard::{LeaderboardInput, SortOrder},
+    score::Score,
+};
+
+mod helper;
+
+#[tokio::test]
+async fn get_scores_sorted_and_ranked() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let alice = helper::create_player(&app, "alice").await;
+    let bob = helper::create_player(&app, "bob").await;
+
+    helper::send_score(&app, &leaderboard, &alice, 10.0).await;
+    helper::send_score(&app, &leaderboard, &bob, 30.0).await;
+    helper::send_score(&app, &leaderboard, &alice, 20.0).await;
+    helper::send_score(&app, &leaderboard, &bob, 20.0).await;
+
+    let scores = client
+        .get(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+
+    assert_eq!(
+        scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![30.0, 20.0, 20.0, 10.0]
+    );
+    assert_eq!(
+        scores.iter().map(|s| s.rank).collect::<Vec<_>>(),
+        vec![1, 2, 2, 4]
+    );
+    assert_eq!(scores[0].player, "bob");
+
+    let scores = client
+        .get(format!(
+            "{}/api/v1/scores/{}?order=asc",
+            app.address, leaderboard.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+
+    assert_eq!(
+        scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![10.0, 20.0, 20.0, 30.0]
+    );
+    // ranks still follow the leaderboard order, the worst score is listed first
+    assert_eq!(
+        scores.iter().map(|s| s.rank).collect::<Vec<_>>(),
+        vec![4, 2, 2, 1]
+    );
+}
+
+#[tokio::test]
+async fn get_scores_paginated() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "alice").await;
+
+    for score in 1..=5 {
+        helper::send_score(&app, &leaderboard, &player, score as f32).await;
+    }
+
+    let scores = client
+        .get(format!(
+            "{}/api/v1/scores/{}?limit=2&offset=1",
+            app.address, leaderboard.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+
+    assert_eq!(
+        scores.iter().map(|s| (s.score, s.rank)).collect::<Vec<_>>(),
+        vec![(4.0, 2), (3.0, 3)]
+    );
+}
+
+#[tokio::test]
+async fn get_scores_invalid_order() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+
+    let response = client
+        .get(format!(
+            "{}/api/v1/scores/{}?order=sideways",
+            app.address, leaderboard.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert!(response.status().is_client_error());
+}
+
+#[tokio::test]
+async fn get_scores_lower_is_better() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard_with(
+        &app,
+        &token,
+        &LeaderboardInput {
+            name: "speedrun".to_string(),
+            sort_order: SortOrder::Ascending,
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(leaderboard.sort_order, SortOrder::Ascending);
+    let player = helper::create_player(&app, "alice").await;
+
+    for score in [12.5, 9.75, 31.0] {
+        helper::send_score(&app, &leaderboard, &player, score).await;
+    }
+
+    let scores = client
+        .get(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+
+    assert_eq!(
+        scores.iter().map(|s| (s.score, s.rank)).collect::<Vec<_>>(),
+        vec![(9.75, 1), (12.5, 2), (31.0, 3)]
+    );
+
+    let scores = client
+        .get(format!(
+            "{}/api/v1/scores/{}?order=desc",
+            app.address, leaderboard.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+
+    assert_eq!(
+        scores.iter().map(|s| (s.score, s.rank)).collect::<Vec<_>>(),
+        vec![(31.0, 3), (12.5, 2), (9.75, 1)]
+    );
+}
@@ -0,0 +1,136 @@
+use jornet_server::domains::{
+    leaderboard::{Leaderboard, LeaderboardInput, ScoreMode, SortOrder},
+    score::Score,
+};
+
+mod helper;
+
+async fn setup(
+    app: &helper::TestApp,
+    sort_order: SortOrder,
+    score_mode: ScoreMode,
+) -> (String, Leaderboard) {
+    let token = helper::admin_token(app).await;
+    let leaderboard = helper::create_leaderboard_with(
+        app,
+        &token,
+        &LeaderboardInput {
+            name: "my leaderboard".to_string(),
+            sort_order,
+            score_mode,
+            ..Default::default()
+        },
+    )
+    .await;
+    let alice = helper::create_player(app, "alice").await;
+    let bob = helper::create_player(app, "bob").await;
+
+    helper::insert_score(app, &leaderboard, &alice, 50.0, 1_000).await;
+    helper::insert_score(app, &leaderboard, &alice, 10.0, 2_000).await;
+    helper::insert_score(app, &leaderboard, &alice, 30.0, 3_000).await;
+    helper::insert_score(app, &leaderboard, &bob, 20.0, 1_500).await;
+    helper::insert_score(app, &leaderboard, &bob, 40.0, 2_500).await;
+
+    (token, leaderboard)
+}
+
+async fn get_scores(app: &helper::TestApp, url: String, token: Option<&str>) -> Vec<Score> {
+    let mut request = reqwest::Client::new().get(format!("{}{}", app.address, url));
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores")
+}
+
+#[tokio::test]
+async fn keep_all_scores() {
+    let app = helper::spawn_app().await;
+    let (_, leaderboard) = setup(&app, SortOrder::Descending, ScoreMode::All).await;
+
+    let scores = get_scores(&app, format!("/api/v1/scores/{}", leaderboard.id), None).await;
+
+    assert_eq!(
+        scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![50.0, 40.0, 30.0, 20.0, 10.0]
+    );
+}
+
+#[tokio::test]
+async fn keep_best_score() {
+    let app = helper::spawn_app().await;
+    let (_, leaderboard) = setup(&app, SortOrder::Descending, ScoreMode::Best).await;
+
+    let scores = get_scores(&app, format!("/api/v1/scores/{}", leaderboard.id), None).await;
+
+    assert_eq!(
+        scores
+            .iter()
+            .map(|s| (s.player.as_str(), s.score, s.rank))
+            .collect::<Vec<_>>(),
+        vec![("alice", 50.0, 1), ("bob", 40.0, 2)]
+    );
+}
+
+#[tokio::test]
+async fn keep_best_score_lower_is_better() {
+    let app = helper::spawn_app().await;
+    let (_, leaderboard) = setup(&app, SortOrder::Ascending, ScoreMode::Best).await;
+
+    let scores = get_scores(&app, format!("/api/v1/scores/{}", leaderboard.id), None).await;
+
+    assert_eq!(
+        scores
+            .iter()
+            .map(|s| (s.player.as_str(), s.score, s.rank))
+            .collect::<Vec<_>>(),
+        vec![("alice", 10.0, 1), ("bob", 20.0, 2)]
+    );
+}
+
+#[tokio::test]
+async fn keep_latest_score() {
+    let app = helper::spawn_app().await;
+    let (_, leaderboard) = setup(&app, SortOrder::Descending, ScoreMode::Latest).await;
+
+    let scores = get_scores(&app, format!("/api/v1/scores/{}", leaderboard.id), None).await;
+
+    assert_eq!(
+        scores
+            .iter()
+            .map(|s| (s.player.as_str(), s.score, s.rank))
+            .collect::<Vec<_>>(),
+        vec![("bob", 40.0, 1), ("alice", 30.0, 2)]
+    );
+}
+
+#[tokio::test]
+async fn admin_get_full_history() {
+    let app = helper::spawn_app().await;
+    let (token, leaderboard) = setup(&app, SortOrder::Descending, ScoreMode::Best).await;
+
+    let scores = get_scores(
+        &app,
+        format!("/api/v1/leaderboards/{}/scores", leaderboard.id),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(scores.len(), 5);
+
+    let other_admin = helper::admin_token(&app).await;
+    let response = reqwest::Client::new()
+        .get(format!(
+            "{}/api/v1/leaderboards/{}/scores",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(other_admin)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,111 @@
+use jornet_server::domains::score::PlayerRanking;
+use uuid::Uuid;
+
+mod helper;
+
+#[tokio::test]
+async fn get_scores_around_player() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let mut players = vec![];
+    for score in 1..=10 {
+        let player = helper::create_player(&app, &format!("player {}", score)).await;
+        helper::insert_score(&app, &leaderboard, &player, score as f32, 1_000).await;
+        players.push(player);
+    }
+
+    let ranking = client
+        .get(format!(
+            "{}/api/v1/scores/{}/around/{}?count=2",
+            app.address, leaderboard.id, players[4].id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<PlayerRanking>()
+        .await
+        .expect("valid ranking");
+
+    assert_eq!(ranking.rank, 6);
+    assert_eq!(
+        ranking
+            .scores
+            .iter()
+            .map(|s| (s.score, s.rank))
+            .collect::<Vec<_>>(),
+        vec![(7.0, 4), (6.0, 5), (5.0, 6), (4.0, 7), (3.0, 8)]
+    );
+
+    let ranking = client
+        .get(format!(
+            "{}/api/v1/scores/{}/around/{}?count=2",
+            app.address, leaderboard.id, players[9].id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<PlayerRanking>()
+        .await
+        .expect("valid ranking");
+
+    assert_eq!(ranking.rank, 1);
+    assert_eq!(
+        ranking.scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+        vec![10.0, 9.0, 8.0]
+    );
+}
+
+#[tokio::test]
+async fn get_scores_around_player_uses_best_score() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let alice = helper::create_player(&app, "alice").await;
+    let bob = helper::create_player(&app, "bob").await;
+    helper::insert_score(&app, &leaderboard, &alice, 10.0, 1_000).await;
+    helper::insert_score(&app, &leaderboard, &bob, 20.0, 1_000).await;
+    helper::insert_score(&app, &leaderboard, &alice, 30.0, 2_000).await;
+
+    let ranking = client
+        .get(format!(
+            "{}/api/v1/scores/{}/around/{}?count=0",
+            app.address, leaderboard.id, alice.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<PlayerRanking>()
+        .await
+        .expect("valid ranking");
+
+    assert_eq!(ranking.rank, 1);
+    assert_eq!(ranking.scores.len(), 1);
+    assert_eq!(ranking.scores[0].score, 30.0);
+}
+
+#[tokio::test]
+async fn get_scores_around_player_without_score() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+
+    let response = client
+        .get(format!(
+            "{}/api/v1/scores/{}/around/{}",
+            app.address,
+            leaderboard.id,
+            Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,105 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jornet_server::domains::score::Score;
+
+mod helper;
+
+async fn get_scores(app: &helper::TestApp, url: String) -> Vec<Score> {
+    reqwest::Client::new()
+        .get(format!("{}{}", app.address, url))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores")
+}
+
+#[tokio::test]
+async fn get_scores_in_range() {
+    let app = helper::spawn_app().await;
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "alice").await;
+    helper::insert_score(&app, &leaderboard, &player, 10.0, 1_000).await;
+    helper::insert_score(&app, &leaderboard, &player, 30.0, 2_000).await;
+    helper::insert_score(&app, &leaderboard, &player, 20.0, 3_000).await;
+
+    let scores = get_scores(
+        &app,
+        format!("/api/v1/scores/{}?since=1500&until=3000", leaderboard.id),
+    )
+    .await;
+    assert_eq!(
+        scores.iter().map(|s| (s.score, s.rank)).collect::<Vec<_>>(),
+        vec![(30.0, 1)]
+    );
+
+    let scores = get_scores(
+        &app,
+        format!("/api/v1/scores/{}?since=2000", leaderboard.id),
+    )
+    .await;
+    assert_eq!(
+        scores.iter().map(|s| (s.score, s.rank)).collect::<Vec<_>>(),
+        vec![(30.0, 1), (20.0, 2)]
+    );
+}
+
+#[tokio::test]
+async fn get_scores_for_period() {
+    let app = helper::spawn_app().await;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "alice").await;
+    helper::insert_score(&app, &leaderboard, &player, 10.0, now).await;
+    helper::insert_score(&app, &leaderboard, &player, 20.0, now - 40 * 24 * 3600).await;
+
+    for period in ["today", "week", "month"] {
+        let scores = get_scores(
+            &app,
+            format!("/api/v1/scores/{}?period={}", leaderboard.id, period),
+        )
+        .await;
+        assert_eq!(
+            scores.iter().map(|s| s.score).collect::<Vec<_>>(),
+            vec![10.0]
+        );
+    }
+
+    let scores = get_scores(
+        &app,
+        format!("/api/v1/scores/{}?period=all", leaderboard.id),
+    )
+    .await;
+    assert_eq!(scores.len(), 2);
+}
+
+#[tokio::test]
+async fn get_scores_invalid_window() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+
+    for query in ["period=today&utc_offset=100000", "period=yesterday"] {
+        let response = client
+            .get(format!(
+                "{}/api/v1/scores/{}?{}",
+                app.address, leaderboard.id, query
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        assert_eq!(response.status(), 400);
+    }
+}
@@ -0,0 +1,178 @@
+use jornet_server::domains::{
+    leaderboard::{Leaderboard, LeaderboardInput, LeaderboardUpdate, ScoreValidation},
+    player::Player,
+    score::ScoreInput,
+};
+
+mod helper;
+
+async fn send(app: &helper::TestApp, leaderboard: &Leaderboard, body: String) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+async fn send_score(
+    app: &helper::TestApp,
+    leaderboard: &Leaderboard,
+    player: &Player,
+    score: f32,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(&ScoreInput::new(
+            score,
+            player.clone(),
+            None,
+            leaderboard.key,
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn reject_scores_out_of_bounds() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard_with(
+        &app,
+        &token,
+        &LeaderboardInput {
+            name: "bounded".to_string(),
+            validation: ScoreValidation {
+                min: Some(0.0),
+                max: Some(100.0),
+                integer_only: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+    let player = helper::create_player(&app, "player").await;
+
+    helper::assert_error(
+        send_score(&app, &leaderboard, &player, -1.0).await,
+        reqwest::StatusCode::BAD_REQUEST,
+        "score_below_min",
+    )
+    .await;
+    helper::assert_error(
+        send_score(&app, &leaderboard, &player, 101.0).await,
+        reqwest::StatusCode::BAD_REQUEST,
+        "score_above_max",
+    )
+    .await;
+    helper::assert_error(
+        send_score(&app, &leaderboard, &player, 5.5).await,
+        reqwest::StatusCode::BAD_REQUEST,
+        "score_not_integer",
+    )
+    .await;
+    assert!(send_score(&app, &leaderboard, &player, 0.0)
+        .await
+        .status()
+        .is_success());
+    assert!(send_score(&app, &leaderboard, &player, 100.0)
+        .await
+        .status()
+        .is_success());
+}
+
+#[tokio::test]
+async fn reject_min_above_max() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let client = reqwest::Client::new();
+    let validation = ScoreValidation {
+        min: Some(10.0),
+        max: Some(5.0),
+        ..Default::default()
+    };
+
+    let response = client
+        .post(format!("{}/api/v1/leaderboards", app.address))
+        .bearer_auth(&token)
+        .json(&LeaderboardInput {
+            name: "impossible".to_string(),
+            validation: validation.clone(),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "invalid_request",
+    )
+    .await;
+
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let response = client
+        .put(format!(
+            "{}/api/v1/leaderboards/{}",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .json(&LeaderboardUpdate {
+            validation: Some(validation),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    helper::assert_error(
+        response,
+        reqwest::StatusCode::BAD_REQUEST,
+        "invalid_request",
+    )
+    .await;
+
+    // equal bounds accept a single score
+    let response = client
+        .put(format!(
+            "{}/api/v1/leaderboards/{}",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .json(&LeaderboardUpdate {
+            validation: Some(ScoreValidation {
+                min: Some(5.0),
+                max: Some(5.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+}
+
+#[tokio::test]
+async fn reject_infinite_score() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    // JSON can't represent infinity, but a number too large for an `f32` is parsed as one
+    let input = ScoreInput::new(f32::INFINITY, player.clone(), None, leaderboard.key);
+    let body = format!(
+        r#"{{"score":1e39,"player":"{}","meta":null,"timestamp":{},"k":"{}","version":{}}}"#,
+        input.player, input.timestamp, input.k, input.version
+    );
+
+    helper::assert_error(
+        send(&app, &leaderboard, body).await,
+        reqwest::StatusCode::BAD_REQUEST,
+        "score_not_finite",
+    )
+    .await;
+}
@@ -0,0 +1,191 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jornet_server::domains::{leaderboard::Leaderboard, score::ScoreInput, time::ServerTime};
+
+mod helper;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+async fn send(
+    app: &helper::TestApp,
+    leaderboard: &Leaderboard,
+    score: &ScoreInput,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .json(score)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn get_server_time() {
+    let app = helper::spawn_app().await;
+
+    let time = reqwest::Client::new()
+        .get(format!("{}/api/v1/time", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<ServerTime>()
+        .await
+        .expect("valid time");
+
+    assert!(time.timestamp.abs_diff(now()) <= 5);
+    assert_eq!(time.score_time_window, 300);
+}
+
+#[tokio::test]
+async fn reject_score_outside_of_time_window() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    for timestamp in [now() - 3600, now() + 3600] {
+        let score = ScoreInput::new_at(10.0, player.clone(), None, leaderboard.key, timestamp);
+        helper::assert_error(
+            send(&app, &leaderboard, &score).await,
+            reqwest::StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        )
+        .await;
+    }
+
+    // a client with a clock slightly off can still send scores
+    let score = ScoreInput::new_at(10.0, player, None, leaderboard.key, now() - 60);
+    assert!(send(&app, &leaderboard, &score).await.status().is_success());
+}
+
+#[tokio::test]
+async fn accept_score_sent_later() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+    let other = helper::create_player(&app, "other").await;
+
+    let made_at = now() - 3600;
+    let score = ScoreInput::new_at(10.0, player.clone(), None, leaderboard.key, made_at);
+    for (sent, status, code) in [
+        // the time it was sent at is signed by the player
+        (
+            score.sent_at(&other, now()),
+            reqwest::StatusCode::UNAUTHORIZED,
+            "invalid_signature",
+        ),
+        // and must be recent
+        (
+            score.sent_at(&player, now() - 3000),
+            reqwest::StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        ),
+        // after the score was made
+        (
+            score.sent_at(&player, made_at - 600),
+            reqwest::StatusCode::BAD_REQUEST,
+            "timestamp_out_of_window",
+        ),
+    ] {
+        helper::assert_error(send(&app, &leaderboard, &sent).await, status, code).await;
+    }
+    // a score can't wait forever
+    let too_old = ScoreInput::new_at(10.0, player.clone(), None, leaderboard.key, 1_000_000);
+    helper::assert_error(
+        send(&app, &leaderboard, &too_old.sent_at(&player, now())).await,
+        reqwest::StatusCode::BAD_REQUEST,
+        "timestamp_out_of_window",
+    )
+    .await;
+
+    let sent = score.sent_at(&player, now());
+    assert!(send(&app, &leaderboard, &sent).await.status().is_success());
+    let saved = sqlx::query!(
+        r#"SELECT EXTRACT(EPOCH FROM timestamp)::BIGINT as "timestamp!" FROM scores WHERE leaderboard = $1"#,
+        leaderboard.id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(saved.timestamp as u64, made_at);
+
+    // sending it again is a duplicate, with or without a new time it was sent at
+    for score in [score.sent_at(&player, now()), score] {
+        helper::assert_error(
+            send(&app, &leaderboard, &score).await,
+            reqwest::StatusCode::CONFLICT,
+            "duplicate_score",
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn reject_replayed_score() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    let mut score = ScoreInput::new(10.0, player, None, leaderboard.key);
+    assert!(send(&app, &leaderboard, &score).await.status().is_success());
+
+    // the same signature with a different encoding
+    score.k = score.k.to_uppercase();
+    helper::assert_error(
+        send(&app, &leaderboard, &score).await,
+        reqwest::StatusCode::CONFLICT,
+        "duplicate_score",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn concurrent_replays_save_score_once() {
+    let app = helper::spawn_app().await;
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+    let player = helper::create_player(&app, "player").await;
+
+    let score = ScoreInput::new(10.0, player, None, leaderboard.key);
+    let requests = (0..10)
+        .map(|_| {
+            reqwest::Client::new()
+                .post(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+                .json(&score)
+                .send()
+        })
+        .map(tokio::spawn)
+        .collect::<Vec<_>>();
+    let mut responses = vec![];
+    for request in requests {
+        responses.push(request.await.unwrap().expect("Failed to execute request."));
+    }
+
+    assert_eq!(
+        responses
+            .iter()
+            .filter(|response| response.status().is_success())
+            .count(),
+        1
+    );
+    assert!(responses
+        .iter()
+        .all(|response| response.status().is_success()
+            || response.status() == reqwest::StatusCode::CONFLICT));
+
+    let saved = sqlx::query!(
+        "SELECT COUNT(*) as count FROM scores WHERE leaderboard = $1",
+        leaderboard.id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(saved.count, Some(1));
+}
@@ -0,0 +1,188 @@
+use jornet_server::domains::{
+    leaderboard::{LeaderboardInput, ScoreMode},
+    season::{NewSeason, Season},
+};
+use serde::Deserialize;
+
+mod helper;
+
+#[derive(Deserialize, Debug)]
+struct Score {
+    score: f32,
+    player: String,
+    rank: i64,
+}
+
+#[tokio::test]
+async fn close_season() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard_with(
+        &app,
+        &token,
+        &LeaderboardInput {
+            name: "my leaderboard".to_string(),
+            score_mode: ScoreMode::Best,
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(leaderboard.season, 1);
+    let alice = helper::create_player(&app, "alice").await;
+    let bob = helper::create_player(&app, "bob").await;
+    helper::send_score(&app, &leaderboard, &alice, 10.0).await;
+    helper::send_score(&app, &leaderboard, &alice, 30.0).await;
+    helper::send_score(&app, &leaderboard, &bob, 20.0).await;
+
+    let new_season = client
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/seasons",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<NewSeason>()
+        .await
+        .expect("valid season");
+    assert_eq!(new_season.season, 2);
+
+    let scores = client
+        .get(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+    assert!(scores.is_empty());
+
+    helper::send_score(&app, &leaderboard, &bob, 5.0).await;
+    let scores = client
+        .get(format!("{}/api/v1/scores/{}", app.address, leaderboard.id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+    assert_eq!(scores.len(), 1);
+    assert_eq!(scores[0].score, 5.0);
+
+    let seasons = client
+        .get(format!(
+            "{}/api/v1/scores/{}/seasons",
+            app.address, leaderboard.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Season>>()
+        .await
+        .expect("valid seasons");
+    assert_eq!(
+        seasons.iter().map(|s| s.season).collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    let standings = client
+        .get(format!(
+            "{}/api/v1/scores/{}/seasons/1",
+            app.address, leaderboard.id
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Vec<Score>>()
+        .await
+        .expect("valid scores");
+    assert_eq!(
+        standings
+            .iter()
+            .map(|s| (s.player.as_str(), s.score, s.rank))
+            .collect::<Vec<_>>(),
+        vec![("alice", 30.0, 1), ("bob", 20.0, 2)]
+    );
+}
+
+#[tokio::test]
+async fn close_season_not_owner() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard(&app, &token).await;
+
+    let other_admin = helper::admin_token(&app).await;
+    let response = client
+        .post(format!(
+            "{}/api/v1/leaderboards/{}/seasons",
+            app.address, leaderboard.id
+        ))
+        .bearer_auth(other_admin)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn close_season_while_saving_scores() {
+    let app = helper::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let token = helper::admin_token(&app).await;
+    let leaderboard = helper::create_leaderboard_with(
+        &app,
+        &token,
+        &LeaderboardInput {
+            name: "my leaderboard".to_string(),
+            score_mode: ScoreMode::All,
+            ..Default::default()
+        },
+    )
+    .await;
+    let alice = helper::create_player(&app, "alice").await;
+
+    let send_scores = async {
+        for score in 0..30 {
+            helper::send_score(&app, &leaderboard, &alice, score as f32).await;
+        }
+    };
+    let close_season = async {
+        let response = client
+            .post(format!(
+                "{}/api/v1/leaderboards/{}/seasons",
+                app.address, leaderboard.id
+            ))
+            .bearer_auth(&token)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert!(response.status().is_success());
+    };
+    tokio::join!(send_scores, close_season);
+
+    // every score saved in the closed season is in its standings
+    let saved = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!" FROM scores WHERE leaderboard = $1 AND season = 1"#,
+        leaderboard.id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+    .count;
+    let archived = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!" FROM season_standings WHERE leaderboard = $1 AND season = 1"#,
+        leaderboard.id
+    )
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+    .count;
+    assert_eq!(saved, archived);
+}